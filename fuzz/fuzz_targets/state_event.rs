@@ -0,0 +1,11 @@
+#![no_main]
+
+use ai_behavior::FuzzCase;
+use libfuzzer_sys::fuzz_target;
+
+// Run with `cargo fuzz run state_event`.
+fuzz_target!(|case: FuzzCase| {
+    if let Err(err) = case.check() {
+        panic!("{}\n{:?}", err, case);
+    }
+});
@@ -0,0 +1,51 @@
+use crate::Behavior;
+
+// Keeps a label on one line, so it does not break the tree.
+fn one_line(label: &str) -> String {
+    label.replace('\r', "").replace('\n', "\\n")
+}
+
+fn write_children<A, F>(out: &mut String, behavior: &Behavior<A>, prefix: &str, action: &mut F)
+where
+    F: FnMut(&A) -> String,
+{
+    let children = behavior.children();
+    let n = children.len();
+    for (i, (role, b)) in children.into_iter().enumerate() {
+        let last = i + 1 == n;
+        out.push_str(prefix);
+        out.push_str(if last { "`-- " } else { "|-- " });
+        if let Some(role) = role {
+            out.push_str(role);
+            out.push_str(": ");
+        }
+        out.push_str(&one_line(&b.label(&mut *action)));
+        out.push('\n');
+        let prefix = format!("{}{}", prefix, if last { "    " } else { "|   " });
+        write_children(out, b, &prefix, action);
+    }
+}
+
+impl<A> Behavior<A> {
+    /// Pretty-prints the behavior tree using ASCII characters.
+    ///
+    /// The closure is used to label actions.
+    ///
+    /// ```text
+    /// Sequence
+    /// |-- Wait(1)
+    /// `-- If
+    ///     |-- condition: Jump
+    ///     |-- success: Land
+    ///     `-- failure: Fall
+    /// ```
+    pub fn to_ascii<F>(&self, mut action: F) -> String
+    where
+        F: FnMut(&A) -> String,
+    {
+        let mut out = one_line(&self.label(&mut action));
+        out.push('\n');
+        write_children(&mut out, self, "", &mut action);
+        out
+    }
+}
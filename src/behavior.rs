@@ -1,10 +1,15 @@
 use input::Button;
 
+use crate::{
+    Action, After, AlwaysSucceed, Fail, If, Select, Sequence, Wait, WaitForPressed,
+    WaitForReleased, WaitForever, WhenAll, WhenAny, While,
+};
+
 /// Describes a behavior.
 ///
 /// This is used for more complex event logic.
 /// Can also be used for game AI.
-#[derive(Clone, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub enum Behavior<A> {
     /// Wait for a button to be pressed.
     ///
@@ -47,6 +52,7 @@ pub enum Behavior<A> {
     /// Succeeds if the conditional behavior succeeds.
     /// Fails if the conditional behavior fails,
     /// or if any behavior in the loop body fails.
+    /// A loop body that takes no time runs at most once per update.
     While(Box<Behavior<A>>, Vec<Behavior<A>>),
     /// Runs all behaviors in parallel until all succeeded.
     ///
@@ -64,3 +70,59 @@ pub enum Behavior<A> {
     /// Fails if one behavior fails.
     After(Vec<Behavior<A>>),
 }
+
+impl<A> Behavior<A> {
+    // Returns the children in path order,
+    // with the role of each child where roles differ.
+    pub(crate) fn children(&self) -> Vec<(Option<&'static str>, &Behavior<A>)> {
+        match *self {
+            WaitForPressed(_) | WaitForReleased(_) | Wait(_) | WaitForever | Action(_) => vec![],
+            Fail(ref ev) | AlwaysSucceed(ref ev) => vec![(None, &**ev)],
+            If(ref condition, ref success, ref failure) => vec![
+                (Some("condition"), &**condition),
+                (Some("success"), &**success),
+                (Some("failure"), &**failure),
+            ],
+            While(ref ev, ref rep) => {
+                let mut children = vec![(Some("condition"), &**ev)];
+                children.extend(rep.iter().map(|b| (Some("body"), b)));
+                children
+            }
+            Select(ref all) | Sequence(ref all) | WhenAll(ref all) | WhenAny(ref all)
+            | After(ref all) => all.iter().map(|b| (None, b)).collect(),
+        }
+    }
+
+    /// Returns the label of the node without its children,
+    /// as used by `to_ascii`, `to_dot` and the other renderers.
+    ///
+    /// The closure labels actions.
+    ///
+    /// ```
+    /// use ai_behavior::{Action, Sequence, Wait};
+    ///
+    /// assert_eq!(Wait(1.5).label(|a: &&str| a.to_string()), "Wait(1.5)");
+    /// assert_eq!(Sequence(vec![Action("jump")]).label(|a| a.to_string()), "Sequence");
+    /// ```
+    pub fn label<F>(&self, mut action: F) -> String
+    where
+        F: FnMut(&A) -> String,
+    {
+        match *self {
+            WaitForPressed(button) => format!("WaitForPressed({:?})", button),
+            WaitForReleased(button) => format!("WaitForReleased({:?})", button),
+            Wait(dt) => format!("Wait({})", dt),
+            WaitForever => "WaitForever".into(),
+            Action(ref a) => action(a),
+            Fail(_) => "Fail".into(),
+            AlwaysSucceed(_) => "AlwaysSucceed".into(),
+            If(..) => "If".into(),
+            Select(_) => "Select".into(),
+            Sequence(_) => "Sequence".into(),
+            While(..) => "While".into(),
+            WhenAll(_) => "WhenAll".into(),
+            WhenAny(_) => "WhenAny".into(),
+            After(_) => "After".into(),
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Validates and renders behavior files.
+
+extern crate ai_behavior;
+extern crate input;
+extern crate ron;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
+
+use std::fs;
+use std::path::Path;
+use std::process;
+
+use ai_behavior::Behavior;
+use serde::de::DeserializeOwned;
+
+mod simulate;
+
+const USAGE: &str = "Usage: ai_behavior <command> <file> [--format <format>]
+       ai_behavior simulate <file> <scenario>
+       ai_behavior diff <file> <new file>
+
+Commands:
+    check      Validates the behavior
+    print      Pretty-prints the behavior, using format `ascii`, `text`, `ron` or `json`
+    dot        Exports the behavior in Graphviz DOT format
+    mermaid    Exports the behavior as a Mermaid flowchart
+    stats      Prints node count, depth and other statistics
+    simulate   Runs the behavior over a scenario and prints the transition log
+    diff       Prints inserted, removed, moved and changed nodes, exiting with 1 if any
+
+Files ending with `.ron` or `.json` are read with Serde,
+other files use the text format of `Behavior::parse`.
+Actions are read as strings.
+
+A scenario is a `.ron` or `.json` file with a timeline of events
+and scripted action outcomes, e.g.
+
+    (
+        events: [Update(1.0), Press(Keyboard(Space)), Update(2.0)],
+        actions: {\"Attack\": (status: Success, after: 2.0)},
+    )";
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
+
+fn fail(file: &str, message: &str) -> ! {
+    eprintln!("{}: {}", file, message);
+    process::exit(1);
+}
+
+fn read(file: &str) -> String {
+    match fs::read_to_string(file) {
+        Ok(text) => text,
+        Err(err) => fail(file, &err.to_string()),
+    }
+}
+
+// Reads a `.ron` or `.json` file, or returns `None` for other extensions.
+fn deserialize<T: DeserializeOwned>(file: &str, text: &str) -> Option<Result<T, String>> {
+    match Path::new(file).extension().and_then(|ext| ext.to_str()) {
+        Some("ron") => Some(ron::from_str(text).map_err(|err| err.to_string())),
+        Some("json") => Some(serde_json::from_str(text).map_err(|err| err.to_string())),
+        _ => None,
+    }
+}
+
+fn load(file: &str) -> Behavior<String> {
+    let text = read(file);
+    let res = deserialize(file, &text).unwrap_or_else(|| {
+        Behavior::parse(&text, |s| Ok(s.to_string())).map_err(|err| err.to_string())
+    });
+    match res {
+        Ok(behavior) => behavior,
+        Err(err) => fail(file, &err),
+    }
+}
+
+// Prints the validation errors and exits if there are any.
+fn validate(file: &str, behavior: &Behavior<String>) {
+    if let Err(errors) = behavior.validate() {
+        for err in &errors {
+            eprintln!("{}: {}", file, err);
+        }
+        process::exit(1);
+    }
+}
+
+fn load_scenario(file: &str) -> simulate::Scenario {
+    let text = read(file);
+    match deserialize(file, &text) {
+        Some(Ok(scenario)) => scenario,
+        Some(Err(err)) => fail(file, &err),
+        None => fail(file, "Expected a `.ron` or `.json` scenario"),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (command, file) = match (args.first(), args.get(1)) {
+        (Some(command), Some(file)) => (&**command, &**file),
+        _ => usage(),
+    };
+    // Commands comparing or combining two files.
+    let two_files = command == "simulate" || command == "diff";
+    let (format, other) = match &args[2..] {
+        [] if !two_files => ("ascii", None),
+        [flag, format] if flag == "--format" && command == "print" => (&**format, None),
+        [other] if two_files => ("ascii", Some(&**other)),
+        _ => usage(),
+    };
+    let behavior = load(file);
+    let label = |a: &String| a.clone();
+    match command {
+        "check" => {
+            validate(file, &behavior);
+            println!("{}: ok", file);
+        }
+        "print" => match format {
+            "ascii" => print!("{}", behavior.to_ascii(label)),
+            "text" => print!("{}", behavior.to_text(label)),
+            "ron" => {
+                let config = ron::ser::PrettyConfig::default();
+                match ron::ser::to_string_pretty(&behavior, config) {
+                    Ok(s) => println!("{}", s),
+                    Err(err) => fail(file, &err.to_string()),
+                }
+            }
+            "json" => match serde_json::to_string_pretty(&behavior) {
+                Ok(s) => println!("{}", s),
+                Err(err) => fail(file, &err.to_string()),
+            },
+            _ => usage(),
+        },
+        "dot" => print!("{}", behavior.to_dot(label)),
+        "mermaid" => print!("{}", behavior.to_mermaid(label)),
+        "stats" => {
+            let stats = behavior.stats();
+            println!("nodes: {}", stats.nodes);
+            println!("depth: {}", stats.depth);
+            println!("actions: {}", stats.actions);
+            println!("leaves: {}", stats.leaves);
+        }
+        "simulate" => {
+            // The state can not be created for invalid behaviors.
+            validate(file, &behavior);
+            let scenario = load_scenario(other.unwrap());
+            for line in simulate::simulate(behavior, &scenario) {
+                println!("{}", line);
+            }
+        }
+        "diff" => {
+            let diff = behavior.diff(&load(other.unwrap()));
+            print!("{}", diff);
+            if !diff.is_empty() {
+                process::exit(1);
+            }
+        }
+        _ => usage(),
+    }
+}
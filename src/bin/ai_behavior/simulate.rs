@@ -0,0 +1,143 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use ai_behavior::{Behavior, Observer, State, Status, Visitor};
+use input::{Button, ButtonArgs, ButtonState, Event, UpdateArgs};
+
+/// An event in the scenario timeline.
+#[derive(Clone, Deserialize, Debug)]
+pub enum Step {
+    /// Advances time by a number of seconds.
+    Update(f64),
+    /// Presses a button.
+    Press(Button),
+    /// Releases a button.
+    Release(Button),
+}
+
+/// The scripted outcome of an action.
+#[derive(Clone, Deserialize, Debug)]
+pub struct Outcome {
+    /// The status returned when the action terminates.
+    pub status: Status,
+    /// The seconds the action runs before it terminates.
+    #[serde(default)]
+    pub after: f64,
+}
+
+/// A timeline of events and how actions behave.
+///
+/// Actions without a scripted outcome keep running.
+#[derive(Clone, Deserialize, Debug)]
+pub struct Scenario {
+    /// The events, in order.
+    pub events: Vec<Step>,
+    /// The outcome of each action.
+    #[serde(default)]
+    pub actions: BTreeMap<String, Outcome>,
+}
+
+// Collects the label of every node.
+struct Labels(BTreeMap<Vec<usize>, String>);
+
+impl Visitor<String> for Labels {
+    fn enter(&mut self, path: &[usize], behavior: &Behavior<String>) {
+        self.0.insert(path.to_vec(), behavior.label(|a| a.clone()));
+    }
+}
+
+// Logs nodes that start, terminate or are aborted.
+struct Log {
+    labels: BTreeMap<Vec<usize>, String>,
+    running: BTreeSet<Vec<usize>>,
+    lines: Vec<String>,
+}
+
+impl Log {
+    fn line(&mut self, what: &str, path: &[usize]) {
+        let label = &self.labels[path];
+        self.lines.push(format!("  {} {:?} {}", what, path, label));
+    }
+}
+
+impl<A, S> Observer<A, S> for Log {
+    fn enter(&mut self, path: &[usize], _state: &State<A, S>) {
+        if self.running.insert(path.to_vec()) {
+            self.line("start", path);
+        }
+    }
+
+    fn exit(&mut self, path: &[usize], status: Status, _dt: f64) {
+        if status != Status::Running {
+            self.running.remove(path);
+            self.line(
+                if status == Status::Success {
+                    "success"
+                } else {
+                    "failure"
+                },
+                path,
+            );
+        }
+    }
+
+    fn abort(&mut self, path: &[usize]) {
+        self.running.remove(path);
+        self.line("abort", path);
+    }
+}
+
+fn button_event(state: ButtonState, button: Button) -> Event {
+    ButtonArgs {
+        state,
+        button,
+        scancode: None,
+    }
+    .into()
+}
+
+/// Runs the behavior over the scenario and returns the transition log.
+///
+/// Stops when the behavior terminates.
+pub fn simulate(behavior: Behavior<String>, scenario: &Scenario) -> Vec<String> {
+    let mut labels = Labels(BTreeMap::new());
+    behavior.visit(&mut labels);
+    let mut log = Log {
+        labels: labels.0,
+        running: BTreeSet::new(),
+        lines: vec![],
+    };
+    let mut state: State<String, f64> = State::new(behavior);
+    let mut t = 0.0;
+    for step in &scenario.events {
+        let e: Event = match *step {
+            Step::Update(dt) => {
+                t += dt;
+                UpdateArgs { dt }.into()
+            }
+            Step::Press(button) => button_event(ButtonState::Press, button),
+            Step::Release(button) => button_event(ButtonState::Release, button),
+        };
+        log.lines.push(format!("{:?} (t = {})", step, t));
+        let (status, _) = state.event_with_observer(
+            &e,
+            &mut |args| match scenario.actions.get(args.action) {
+                Some(outcome) => {
+                    let elapsed = args.state.get_or_insert(0.0);
+                    *elapsed += args.dt;
+                    if *elapsed >= outcome.after {
+                        (outcome.status, *elapsed - outcome.after)
+                    } else {
+                        (Status::Running, 0.0)
+                    }
+                }
+                None => (Status::Running, 0.0),
+            },
+            &mut log,
+        );
+        if status != Status::Running {
+            log.lines.push(format!("{:?} (t = {})", status, t));
+            break;
+        }
+    }
+    log.lines
+}
@@ -0,0 +1,153 @@
+use crate::{
+    After, AlwaysSucceed, Behavior, Fail, If, Select, Sequence, Wait, WhenAll, WhenAny, While,
+};
+
+/// The kind of behavior being built.
+#[derive(Clone, Debug)]
+enum Kind<A> {
+    Select,
+    Sequence,
+    WhenAll,
+    WhenAny,
+    After,
+    While(Box<Behavior<A>>),
+}
+
+/// Builds a composite behavior one child at a time.
+///
+/// Created by e.g. `Behavior::sequence()`.
+/// Builders can be passed to `then` directly, without calling `build`.
+///
+/// ```
+/// use ai_behavior::{Action, Behavior, Sequence, Wait};
+///
+/// let b = Behavior::sequence().then(Action("jump")).then_wait(1.0).build();
+/// assert!(b == Sequence(vec![Action("jump"), Wait(1.0)]));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Builder<A> {
+    kind: Kind<A>,
+    children: Vec<Behavior<A>>,
+}
+
+impl<A> Builder<A> {
+    fn new(kind: Kind<A>) -> Builder<A> {
+        Builder {
+            kind,
+            children: vec![],
+        }
+    }
+
+    /// Adds a child behavior.
+    pub fn then<B: Into<Behavior<A>>>(mut self, behavior: B) -> Self {
+        self.children.push(behavior.into());
+        self
+    }
+
+    /// Adds an action.
+    pub fn then_action(self, action: A) -> Self {
+        self.then(Behavior::Action(action))
+    }
+
+    /// Adds a wait in seconds.
+    pub fn then_wait(self, dt: f64) -> Self {
+        self.then(Wait(dt))
+    }
+
+    /// Adds many child behaviors.
+    pub fn then_all<I>(mut self, behaviors: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Behavior<A>>,
+    {
+        self.children
+            .extend(behaviors.into_iter().map(|b| b.into()));
+        self
+    }
+
+    /// Creates the behavior.
+    ///
+    /// `WhenAll`, `WhenAny` and `After` without children terminate
+    /// on the first event with the whole delta time left:
+    /// `WhenAll` and `After` succeed and `WhenAny` fails.
+    /// An empty `Sequence` is built as `WhenAll` and an empty `Select` as `WhenAny`,
+    /// because they need a child to start.
+    /// A `While` without a body behaves like its condition.
+    pub fn build(self) -> Behavior<A> {
+        match self.kind {
+            Kind::Select if self.children.is_empty() => WhenAny(vec![]),
+            Kind::Sequence if self.children.is_empty() => WhenAll(vec![]),
+            Kind::While(condition) if self.children.is_empty() => *condition,
+            Kind::Select => Select(self.children),
+            Kind::Sequence => Sequence(self.children),
+            Kind::WhenAll => WhenAll(self.children),
+            Kind::WhenAny => WhenAny(self.children),
+            Kind::After => After(self.children),
+            Kind::While(condition) => While(condition, self.children),
+        }
+    }
+}
+
+impl<A> From<Builder<A>> for Behavior<A> {
+    fn from(builder: Builder<A>) -> Behavior<A> {
+        builder.build()
+    }
+}
+
+impl<A> Behavior<A> {
+    /// Starts building a `Select` behavior.
+    pub fn select() -> Builder<A> {
+        Builder::new(Kind::Select)
+    }
+
+    /// Starts building a `Sequence` behavior.
+    pub fn sequence() -> Builder<A> {
+        Builder::new(Kind::Sequence)
+    }
+
+    /// Starts building a `WhenAll` behavior.
+    pub fn when_all() -> Builder<A> {
+        Builder::new(Kind::WhenAll)
+    }
+
+    /// Starts building a `WhenAny` behavior.
+    pub fn when_any() -> Builder<A> {
+        Builder::new(Kind::WhenAny)
+    }
+
+    /// Starts building an `After` behavior.
+    pub fn after() -> Builder<A> {
+        Builder::new(Kind::After)
+    }
+
+    /// Starts building a `While` behavior with a condition.
+    ///
+    /// The children are the loop body.
+    pub fn repeat_while<B: Into<Behavior<A>>>(condition: B) -> Builder<A> {
+        Builder::new(Kind::While(Box::new(condition.into())))
+    }
+
+    /// Creates an `If` behavior.
+    pub fn if_else<C, S, F>(condition: C, success: S, failure: F) -> Behavior<A>
+    where
+        C: Into<Behavior<A>>,
+        S: Into<Behavior<A>>,
+        F: Into<Behavior<A>>,
+    {
+        If(
+            Box::new(condition.into()),
+            Box::new(success.into()),
+            Box::new(failure.into()),
+        )
+    }
+
+    /// Converts `Success` into `Failure` and vice versa, by wrapping in `Fail`.
+    pub fn inverted(self) -> Behavior<A> {
+        Fail(Box::new(self))
+    }
+
+    /// Ignores failures, by wrapping in `AlwaysSucceed`.
+    pub fn always_succeed(self) -> Behavior<A> {
+        AlwaysSucceed(Box::new(self))
+    }
+}
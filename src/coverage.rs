@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::{
+    Action, After, AlwaysSucceed, Behavior, Fail, Failure, If, Observer, Running, Select, Sequence,
+    Status, Success, Wait, WaitForPressed, WaitForReleased, WaitForever, WhenAll, WhenAny, While,
+};
+
+/// The outcomes a node returned.
+#[derive(Copy, Clone, Default, Deserialize, Serialize, PartialEq, Eq, Debug)]
+pub struct Outcomes {
+    /// The node returned `Success`.
+    pub success: bool,
+    /// The node returned `Failure`.
+    pub failure: bool,
+    /// The node returned `Running`.
+    pub running: bool,
+}
+
+/// An observer that marks which nodes were updated and what they returned.
+///
+/// The same coverage can be passed to many states using the same behavior.
+/// Use `Coverage::report` to compare against the behavior.
+#[derive(Clone, Default, Deserialize, Serialize, PartialEq, Debug)]
+pub struct Coverage {
+    /// The outcomes of every node that was updated, keyed by path.
+    pub nodes: BTreeMap<Vec<usize>, Outcomes>,
+}
+
+impl Coverage {
+    /// Creates a new empty coverage.
+    pub fn new() -> Coverage {
+        Coverage {
+            nodes: BTreeMap::new(),
+        }
+    }
+
+    /// Compares the coverage with every node of the behavior.
+    pub fn report<A: fmt::Debug>(&self, behavior: &Behavior<A>) -> CoverageReport {
+        let mut report = CoverageReport { nodes: vec![] };
+        self.collect(behavior, &mut vec![], &mut report);
+        report
+    }
+
+    fn collect<A: fmt::Debug>(
+        &self,
+        behavior: &Behavior<A>,
+        path: &mut Vec<usize>,
+        report: &mut CoverageReport,
+    ) {
+        let outcomes = self.nodes.get(&path[..]);
+        let expected = terminal_outcomes(behavior);
+        let missing = expected
+            .iter()
+            .cloned()
+            .filter(|&status| match (status, outcomes) {
+                (_, None) => true,
+                (Success, Some(outcomes)) => !outcomes.success,
+                (Failure, Some(outcomes)) => !outcomes.failure,
+                (Running, Some(outcomes)) => !outcomes.running,
+            })
+            .collect();
+        report.nodes.push(NodeCoverage {
+            path: path.clone(),
+            label: behavior.label(|a| format!("{:?}", a)),
+            hit: outcomes.is_some(),
+            expected: expected.to_vec(),
+            missing,
+        });
+        for (i, (_, child)) in behavior.children().into_iter().enumerate() {
+            path.push(i);
+            self.collect(child, path, report);
+            path.pop();
+        }
+    }
+}
+
+// The terminal outcomes a behavior is expected to be able to return.
+fn terminal_outcomes<A>(behavior: &Behavior<A>) -> &'static [Status] {
+    match *behavior {
+        WaitForever => &[],
+        Wait(_) | WaitForPressed(_) | WaitForReleased(_) | AlwaysSucceed(_) => &[Success],
+        Action(_) | Fail(_) | If(..) | Select(_) | Sequence(_) | While(..) | WhenAll(_)
+        | WhenAny(_) | After(_) => &[Success, Failure],
+    }
+}
+
+impl<A, S> Observer<A, S> for Coverage {
+    fn exit(&mut self, path: &[usize], status: Status, _dt: f64) {
+        let outcomes = self.nodes.entry(path.to_vec()).or_default();
+        match status {
+            Success => outcomes.success = true,
+            Failure => outcomes.failure = true,
+            Running => outcomes.running = true,
+        }
+    }
+}
+
+/// The coverage of a single node.
+#[derive(Clone, PartialEq, Debug)]
+pub struct NodeCoverage {
+    /// The path of the node.
+    pub path: Vec<usize>,
+    /// The label of the node.
+    pub label: String,
+    /// Whether the node was updated at least once.
+    pub hit: bool,
+    /// The terminal outcomes the node can return.
+    pub expected: Vec<Status>,
+    /// The terminal outcomes the node never returned.
+    pub missing: Vec<Status>,
+}
+
+/// Compares the coverage with a behavior.
+#[derive(Clone, PartialEq, Debug)]
+pub struct CoverageReport {
+    /// Every node in the behavior, in depth-first order.
+    pub nodes: Vec<NodeCoverage>,
+}
+
+impl CoverageReport {
+    /// Returns the fraction of nodes that were hit.
+    pub fn node_coverage(&self) -> f64 {
+        let hit = self.nodes.iter().filter(|node| node.hit).count();
+        ratio(hit, self.nodes.len())
+    }
+
+    /// Returns the fraction of terminal outcomes that were returned.
+    ///
+    /// `Success` and `Failure` are expected from every node that can return them,
+    /// e.g. `Wait` can only succeed and `WaitForever` never terminates.
+    pub fn outcome_coverage(&self) -> f64 {
+        let (mut total, mut missing) = (0, 0);
+        for node in &self.nodes {
+            total += node.expected.len();
+            missing += node.missing.len();
+        }
+        ratio(total - missing, total)
+    }
+
+    /// Returns the nodes that were not hit or have missing outcomes.
+    pub fn uncovered(&self) -> impl Iterator<Item = &NodeCoverage> {
+        self.nodes
+            .iter()
+            .filter(|node| !node.hit || !node.missing.is_empty())
+    }
+
+    /// Panics with the report if the node or outcome coverage is below `min`.
+    ///
+    /// This is used to fail a test when coverage of a tree drops.
+    pub fn assert_min(&self, min: f64) {
+        if self.node_coverage() < min || self.outcome_coverage() < min {
+            panic!("Coverage is below {}\n{}", min, self);
+        }
+    }
+}
+
+fn ratio(n: usize, total: usize) -> f64 {
+    if total == 0 {
+        1.0
+    } else {
+        n as f64 / total as f64
+    }
+}
+
+/// Writes a summary and the uncovered nodes.
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "nodes: {:.1}%, outcomes: {:.1}%",
+            100.0 * self.node_coverage(),
+            100.0 * self.outcome_coverage()
+        )?;
+        for node in self.uncovered() {
+            if node.hit {
+                writeln!(
+                    f,
+                    "{:?} {}: missing {:?}",
+                    node.path, node.label, node.missing
+                )?;
+            } else {
+                writeln!(f, "{:?} {}: not hit", node.path, node.label)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+use std::fmt;
+use std::mem::discriminant;
+
+use crate::{AlwaysSucceed, Behavior, Fail, If, While};
+
+/// A change between two behavior trees.
+///
+/// Paths use the same numbering as `State::active_paths`,
+/// in the old tree for `from` and in the new tree for `to`.
+#[derive(Clone, PartialEq, Debug)]
+pub enum TreeChange {
+    /// A subtree was inserted.
+    Inserted {
+        /// The path in the new tree.
+        to: Vec<usize>,
+        /// The label of the inserted node.
+        label: String,
+    },
+    /// A subtree was removed.
+    Removed {
+        /// The path in the old tree.
+        from: Vec<usize>,
+        /// The label of the removed node.
+        label: String,
+    },
+    /// An unchanged subtree was moved among its siblings.
+    Moved {
+        /// The path in the old tree.
+        from: Vec<usize>,
+        /// The path in the new tree.
+        to: Vec<usize>,
+        /// The label of the moved node.
+        label: String,
+    },
+    /// A node was changed, e.g. a different action or wait time.
+    /// Changes of its children are reported separately.
+    Changed {
+        /// The path in the old tree.
+        from: Vec<usize>,
+        /// The path in the new tree.
+        to: Vec<usize>,
+        /// The old label.
+        old: String,
+        /// The new label.
+        new: String,
+    },
+}
+
+impl fmt::Display for TreeChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            TreeChange::Inserted { ref to, ref label } => write!(f, "+ {:?} {}", to, label),
+            TreeChange::Removed {
+                ref from,
+                ref label,
+            } => write!(f, "- {:?} {}", from, label),
+            TreeChange::Moved {
+                ref from,
+                ref to,
+                ref label,
+            } => write!(f, "> {:?} -> {:?} {}", from, to, label),
+            TreeChange::Changed {
+                ref from,
+                ref to,
+                ref old,
+                ref new,
+            } => write!(f, "~ {:?} -> {:?} {} => {}", from, to, old, new),
+        }
+    }
+}
+
+/// The changes between two behavior trees, created by `Behavior::diff`.
+///
+/// Displayed with one change per line:
+/// `+` inserted, `-` removed, `>` moved and `~` changed.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct TreeDiff {
+    /// The changes, in depth-first order.
+    pub changes: Vec<TreeChange>,
+}
+
+impl TreeDiff {
+    /// Returns `true` if the trees are equal.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for TreeDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+struct Differ {
+    changes: Vec<TreeChange>,
+}
+
+fn label<A: fmt::Debug>(behavior: &Behavior<A>) -> String {
+    behavior.label(|a| format!("{:?}", a))
+}
+
+fn child_path(path: &[usize], i: usize) -> Vec<usize> {
+    let mut path = path.to_vec();
+    path.push(i);
+    path
+}
+
+impl Differ {
+    fn node<A: PartialEq + fmt::Debug>(
+        &mut self,
+        old: &Behavior<A>,
+        new: &Behavior<A>,
+        from: &[usize],
+        to: &[usize],
+    ) {
+        if old == new {
+            return;
+        }
+        let (old_label, new_label) = (label(old), label(new));
+        if old_label != new_label {
+            self.changes.push(TreeChange::Changed {
+                from: from.to_vec(),
+                to: to.to_vec(),
+                old: old_label,
+                new: new_label,
+            });
+        }
+        let old_children: Vec<_> = old.children().into_iter().map(|(_, b)| b).collect();
+        let new_children: Vec<_> = new.children().into_iter().map(|(_, b)| b).collect();
+        match (old, new) {
+            // Children with roles are compared by role.
+            (&Fail(_), &Fail(_)) | (&AlwaysSucceed(_), &AlwaysSucceed(_)) | (&If(..), &If(..)) => {
+                for (i, (a, b)) in old_children.iter().zip(&new_children).enumerate() {
+                    self.node(a, b, &child_path(from, i), &child_path(to, i));
+                }
+            }
+            (&While(..), &While(..)) => {
+                self.node(
+                    old_children[0],
+                    new_children[0],
+                    &child_path(from, 0),
+                    &child_path(to, 0),
+                );
+                self.list(&old_children[1..], &new_children[1..], 1, from, to);
+            }
+            _ => self.list(&old_children, &new_children, 0, from, to),
+        }
+    }
+
+    // Compares lists of children, starting at index `offset` among siblings.
+    fn list<A: PartialEq + fmt::Debug>(
+        &mut self,
+        old: &[&Behavior<A>],
+        new: &[&Behavior<A>],
+        offset: usize,
+        from: &[usize],
+        to: &[usize],
+    ) {
+        let (n, m) = (old.len(), new.len());
+        // Longest common subsequence of equal subtrees.
+        let mut lcs = vec![vec![0; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i][j] = if old[i] == new[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+        // Collect the unmatched children between common ones.
+        let mut gaps: Vec<(Vec<usize>, Vec<usize>)> = vec![(vec![], vec![])];
+        let (mut i, mut j) = (0, 0);
+        while i < n || j < m {
+            if i < n && j < m && old[i] == new[j] {
+                gaps.push((vec![], vec![]));
+                i += 1;
+                j += 1;
+            } else if j == m || (i < n && lcs[i + 1][j] >= lcs[i][j + 1]) {
+                gaps.last_mut().unwrap().0.push(i);
+                i += 1;
+            } else {
+                gaps.last_mut().unwrap().1.push(j);
+                j += 1;
+            }
+        }
+        // Equal subtrees in different gaps were moved.
+        let mut moved_old = vec![false; n];
+        let mut moved_new = vec![false; m];
+        let mut moves = vec![];
+        for &i in gaps.iter().flat_map(|g| &g.0) {
+            let found = gaps
+                .iter()
+                .flat_map(|g| &g.1)
+                .find(|&&j| !moved_new[j] && old[i] == new[j]);
+            if let Some(&j) = found {
+                moved_old[i] = true;
+                moved_new[j] = true;
+                moves.push(TreeChange::Moved {
+                    from: child_path(from, offset + i),
+                    to: child_path(to, offset + j),
+                    label: label(old[i]),
+                });
+            }
+        }
+        for (old_gap, new_gap) in gaps {
+            let mut old_gap = old_gap.into_iter().filter(|&i| !moved_old[i]).peekable();
+            let mut new_gap = new_gap.into_iter().filter(|&j| !moved_new[j]).peekable();
+            // Children of the same kind at the same place were changed.
+            while let (Some(&i), Some(&j)) = (old_gap.peek(), new_gap.peek()) {
+                if discriminant(old[i]) != discriminant(new[j]) {
+                    break;
+                }
+                self.node(
+                    old[i],
+                    new[j],
+                    &child_path(from, offset + i),
+                    &child_path(to, offset + j),
+                );
+                old_gap.next();
+                new_gap.next();
+            }
+            for i in old_gap {
+                self.changes.push(TreeChange::Removed {
+                    from: child_path(from, offset + i),
+                    label: label(old[i]),
+                });
+            }
+            for j in new_gap {
+                self.changes.push(TreeChange::Inserted {
+                    to: child_path(to, offset + j),
+                    label: label(new[j]),
+                });
+            }
+        }
+        self.changes.extend(moves);
+    }
+}
+
+impl<A: PartialEq + fmt::Debug> Behavior<A> {
+    /// Compares the behavior with a new version,
+    /// reporting inserted, removed, moved and changed nodes.
+    ///
+    /// Siblings are matched by finding the most subtrees that are unchanged.
+    /// Remaining siblings of the same kind at the same place are compared
+    /// node by node, the rest are reported as inserted or removed.
+    ///
+    /// ```
+    /// use ai_behavior::{Action, Sequence, Wait};
+    ///
+    /// let old = Sequence(vec![Action("aim"), Wait(1.0), Action("fire")]);
+    /// let new = Sequence(vec![Wait(2.0), Action("fire"), Action("aim")]);
+    /// assert_eq!(
+    ///     old.diff(&new).to_string(),
+    ///     "~ [1] -> [0] Wait(1) => Wait(2)\n> [0] -> [2] \"aim\"\n"
+    /// );
+    /// ```
+    pub fn diff(&self, new: &Behavior<A>) -> TreeDiff {
+        let mut differ = Differ { changes: vec![] };
+        differ.node(self, new, &[], &[]);
+        TreeDiff {
+            changes: differ.changes,
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use std::fmt::Write;
+
+use crate::state::State::{
+    self, ActionState, AfterState, AlwaysSucceedState, FailState, IfState, SelectState,
+    SequenceState, WaitForPressedState, WaitForReleasedState, WaitForeverState, WaitState,
+    WhenAllState, WhenAnyState, WhileState,
+};
+use crate::{Behavior, Failure, Running, Status, Success};
+
+/// How a node is drawn.
+#[derive(Copy, Clone, PartialEq)]
+enum Style {
+    /// Plain behavior node without any state.
+    Plain,
+    /// The node has not been started yet.
+    Pending,
+    /// The node terminated or is running.
+    Status(Status),
+}
+
+impl Style {
+    fn attributes(self) -> &'static str {
+        match self {
+            Style::Plain => "",
+            Style::Pending => ", style=dashed, fontcolor=gray40, color=gray40",
+            Style::Status(Running) => ", style=\"filled,bold\", fillcolor=gold",
+            Style::Status(Success) => ", style=filled, fillcolor=palegreen",
+            Style::Status(Failure) => ", style=filled, fillcolor=lightpink",
+        }
+    }
+}
+
+/// Accumulates the DOT output and assigns node ids.
+struct Dot<F> {
+    out: String,
+    next: usize,
+    action: F,
+}
+
+impl<F> Dot<F> {
+    fn new(action: F) -> Dot<F> {
+        let mut out = String::new();
+        out.push_str("digraph behavior {\n");
+        out.push_str("    node [shape=box];\n");
+        Dot {
+            out,
+            next: 0,
+            action,
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.out.push_str("}\n");
+        self.out
+    }
+
+    fn node(&mut self, label: &str, style: Style) -> usize {
+        let id = self.next;
+        self.next += 1;
+        let _ = writeln!(
+            self.out,
+            "    n{} [label=\"{}\"{}];",
+            id,
+            escape(label),
+            style.attributes()
+        );
+        id
+    }
+
+    fn edge(&mut self, from: usize, to: usize, label: Option<&str>) {
+        let _ = match label {
+            Some(label) => writeln!(self.out, "    n{} -> n{} [label=\"{}\"];", from, to, label),
+            None => writeln!(self.out, "    n{} -> n{};", from, to),
+        };
+    }
+
+    fn behavior<A>(&mut self, behavior: &Behavior<A>, style: Style) -> usize
+    where
+        F: FnMut(&A) -> String,
+    {
+        let label = behavior.label(&mut self.action);
+        let id = self.node(&label, style);
+        for (edge, b) in behavior.children() {
+            let child = self.behavior(b, style);
+            self.edge(id, child, edge);
+        }
+        id
+    }
+
+    fn state<A, S>(&mut self, state: &State<A, S>, style: Style) -> usize
+    where
+        F: FnMut(&A) -> String,
+    {
+        match *state {
+            WaitForPressedState(button) => {
+                self.node(&format!("WaitForPressed({:?})", button), style)
+            }
+            WaitForReleasedState(button) => {
+                self.node(&format!("WaitForReleased({:?})", button), style)
+            }
+            ActionState(ref action, ..) => {
+                let label = (self.action)(action);
+                self.node(&label, style)
+            }
+            FailState(ref cur) => {
+                let id = self.node("Fail", style);
+                let child = self.state(cur, style);
+                self.edge(id, child, None);
+                id
+            }
+            AlwaysSucceedState(ref cur) => {
+                let id = self.node("AlwaysSucceed", style);
+                let child = self.state(cur, style);
+                self.edge(id, child, None);
+                id
+            }
+            WaitState(wait_t, t) => self.node(&format!("Wait({}/{})", t, wait_t), style),
+            WaitForeverState => self.node("WaitForever", style),
+            IfState(ref condition, ref success, ref failure, status, ref cur) => {
+                let id = self.node("If", style);
+                let (condition, success, failure) = match status {
+                    Running => (
+                        self.state(cur, style),
+                        self.behavior(success, Style::Pending),
+                        self.behavior(failure, Style::Pending),
+                    ),
+                    Success => (
+                        self.behavior(condition, Style::Status(Success)),
+                        self.state(cur, style),
+                        self.behavior(failure, Style::Pending),
+                    ),
+                    Failure => (
+                        self.behavior(condition, Style::Status(Failure)),
+                        self.behavior(success, Style::Pending),
+                        self.state(cur, style),
+                    ),
+                };
+                self.edge(id, condition, Some("condition"));
+                self.edge(id, success, Some("success"));
+                self.edge(id, failure, Some("failure"));
+                id
+            }
+            SelectState(ref seq, i, ref cur) => {
+                let id = self.node("Select", style);
+                self.children(id, style, Failure, seq, i, cur, None);
+                id
+            }
+            SequenceState(ref seq, i, ref cur) => {
+                let id = self.node("Sequence", style);
+                self.children(id, style, Success, seq, i, cur, None);
+                id
+            }
+            WhileState(ref ev_cursor, ref rep, i, ref cur) => {
+                let id = self.node("While", style);
+                let child = self.state(ev_cursor, style);
+                self.edge(id, child, Some("condition"));
+                self.children(id, style, Success, rep, i, cur, Some("body"));
+                id
+            }
+            WhenAllState(ref all, ref cursors) => {
+                self.when_all("WhenAll", style, Success, all, cursors)
+            }
+            WhenAnyState(ref all, ref cursors) => {
+                self.when_all("WhenAny", style, Failure, all, cursors)
+            }
+            AfterState(i, ref cursors) => {
+                let id = self.node("After", style);
+                for (j, cur) in cursors.iter().enumerate() {
+                    let child = if j < i {
+                        self.state(cur, Style::Status(Success))
+                    } else {
+                        self.state(cur, style)
+                    };
+                    self.edge(id, child, None);
+                }
+                id
+            }
+        }
+    }
+
+    // Draws the children of `Sequence`, `Select` and the body of `While`.
+    //
+    // Behaviors before the cursor terminated with `done`,
+    // behaviors after the cursor are pending.
+    #[allow(clippy::too_many_arguments)]
+    fn children<A, S>(
+        &mut self,
+        id: usize,
+        style: Style,
+        done: Status,
+        seq: &[Behavior<A>],
+        i: usize,
+        cur: &State<A, S>,
+        edge: Option<&str>,
+    ) where
+        F: FnMut(&A) -> String,
+    {
+        for (j, b) in seq.iter().enumerate() {
+            let child = if j < i {
+                self.behavior(b, Style::Status(done))
+            } else if j == i {
+                self.state(cur, style)
+            } else {
+                self.behavior(b, Style::Pending)
+            };
+            self.edge(id, child, edge);
+        }
+    }
+
+    fn when_all<A, S>(
+        &mut self,
+        label: &str,
+        style: Style,
+        done: Status,
+        all: &[Behavior<A>],
+        cursors: &[Option<State<A, S>>],
+    ) -> usize
+    where
+        F: FnMut(&A) -> String,
+    {
+        let id = self.node(label, style);
+        for (ev, cur) in all.iter().zip(cursors) {
+            let child = match *cur {
+                Some(ref cur) => self.state(cur, style),
+                None => self.behavior(ev, Style::Status(done)),
+            };
+            self.edge(id, child, None);
+        }
+        id
+    }
+}
+
+fn escape(label: &str) -> String {
+    label
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\r', "")
+        .replace('\n', "\\n")
+}
+
+impl<A> Behavior<A> {
+    /// Exports the behavior tree in Graphviz DOT format.
+    ///
+    /// The closure is used to label actions.
+    pub fn to_dot<F>(&self, action: F) -> String
+    where
+        F: FnMut(&A) -> String,
+    {
+        let mut dot = Dot::new(action);
+        dot.behavior(self, Style::Plain);
+        dot.finish()
+    }
+}
+
+impl<A, S> State<A, S> {
+    /// Exports a snapshot of the state in Graphviz DOT format.
+    ///
+    /// Running nodes are highlighted, terminated nodes are colored by status
+    /// and nodes that have not started yet are dashed.
+    /// Waits are labeled with elapsed and total time.
+    ///
+    /// The closure is used to label actions.
+    pub fn to_dot<F>(&self, action: F) -> String
+    where
+        F: FnMut(&A) -> String,
+    {
+        let mut dot = Dot::new(action);
+        dot.state(self, Style::Status(Running));
+        dot.finish()
+    }
+}
@@ -0,0 +1,36 @@
+use std::error::Error;
+use std::fmt;
+
+/// A reason given by an action with `ActionArgs::fail`.
+pub type Reason = Box<dyn Error + Send + Sync>;
+
+/// Why a behavior failed, returned by `State::event_with_reason`
+/// and `State::handle_with_reason`.
+#[derive(Debug)]
+pub struct FailureReason {
+    /// The path of the node where the failure started,
+    /// as used by `State::active_paths`.
+    pub path: Vec<usize>,
+    /// The remaining delta time.
+    pub dt: f64,
+    /// The reason given by the action, if any.
+    pub reason: Option<Reason>,
+}
+
+impl fmt::Display for FailureReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.reason {
+            Some(ref reason) => write!(f, "{:?} failed: {}", self.path, reason),
+            None => write!(f, "{:?} failed", self.path),
+        }
+    }
+}
+
+impl Error for FailureReason {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self.reason {
+            Some(ref reason) => Some(&**reason),
+            None => None,
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+use input::GenericEvent;
+
+use crate::{Running, State, Status, RUNNING};
+
+/// A future run by an action, stored in the action state.
+pub type ActionFuture = Pin<Box<dyn Future<Output = Status>>>;
+
+// A waker that does nothing, for games that poll every frame anyway.
+struct Noop;
+
+impl Wake for Noop {
+    fn wake(self: Arc<Self>) {}
+}
+
+thread_local! {
+    // Created once per thread, so polling does not allocate.
+    static NOOP: Waker = Waker::from(Arc::new(Noop));
+}
+
+impl<A: Clone, T> State<A, T>
+where
+    T: Future<Output = Status> + Unpin,
+{
+    /// Updates the cursor like `event`, running actions as futures.
+    ///
+    /// When an action starts, the closure creates its future,
+    /// which is kept in the action state and polled on every event
+    /// until it is ready. The future is dropped when it is ready,
+    /// or when the action is stopped because its parent terminated.
+    ///
+    /// A future that is ready with `Running` is created again on the next event.
+    ///
+    /// ```
+    /// use ai_behavior::{Action, ActionFuture, Sequence, State, Success, Wait};
+    /// use input::{Event, UpdateArgs};
+    ///
+    /// let mut state: State<_, ActionFuture> =
+    ///     State::new(Sequence(vec![Action("load"), Wait(1.0)]));
+    /// let e: Event = UpdateArgs { dt: 0.5 }.into();
+    /// state.event_async(&e, &mut |_| Box::pin(async { Success }));
+    /// assert_eq!(state.active_paths(), vec![vec![1]]);
+    /// ```
+    pub fn event_async<E, F>(&mut self, e: &E, f: &mut F) -> (Status, f64)
+    where
+        E: GenericEvent,
+        F: FnMut(&A) -> T,
+    {
+        NOOP.with(|waker| self.event_async_with_waker(e, waker, f))
+    }
+
+    /// Updates the cursor like `event_async`,
+    /// polling futures with a waker that is notified when they can make progress.
+    pub fn event_async_with_waker<E, F>(&mut self, e: &E, waker: &Waker, f: &mut F) -> (Status, f64)
+    where
+        E: GenericEvent,
+        F: FnMut(&A) -> T,
+    {
+        let mut cx = Context::from_waker(waker);
+        self.event(e, &mut |args| {
+            let action = args.action;
+            let future = args.state.get_or_insert_with(|| f(action));
+            match Pin::new(future).poll(&mut cx) {
+                Poll::Pending => RUNNING,
+                Poll::Ready(status) => {
+                    *args.state = None;
+                    match status {
+                        Running => RUNNING,
+                        status => (status, args.dt),
+                    }
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,191 @@
+use std::error::Error;
+use std::fmt;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use input::{Button, ButtonArgs, ButtonState, Event, Input, Key, Loop, MouseButton, UpdateArgs};
+
+use crate::{
+    ActionArgs, After, AlwaysSucceed, Behavior, Fail, Failure, If, Observer, RecordedEvent,
+    Running, Select, Sequence, State, Status, Success, Wait, WaitForPressed, WaitForReleased,
+    WaitForever, WhenAll, WhenAny, While,
+};
+
+/// A random behavior tree with a random stream of events.
+///
+/// Generated with `Arbitrary`, e.g. from the bytes of a fuzzer
+/// or from a byte vector generated by a property testing framework.
+/// The behavior passes `Behavior::validate`, so `State::new` never panics.
+///
+/// Delta times are multiples of 0.25 seconds,
+/// to make children terminate at the same time often.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FuzzCase {
+    /// The behavior.
+    pub behavior: Behavior<u8>,
+    /// The events, in order.
+    pub events: Vec<RecordedEvent>,
+}
+
+/// An invariant of `State::event` that did not hold.
+#[derive(Clone, PartialEq, Debug)]
+pub struct InvariantError {
+    /// The index of the event.
+    pub event: usize,
+    /// Describes the invariant.
+    pub message: String,
+}
+
+impl fmt::Display for InvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invariant broken at event {}: {}",
+            self.event, self.message
+        )
+    }
+}
+
+impl Error for InvariantError {}
+
+const MAX_DEPTH: u32 = 5;
+
+// Checks the delta time returned by every node
+// against the delta time the node was given.
+struct DtCheck {
+    // The delta time given to each entered node, 0 for other events.
+    given: Vec<f64>,
+    error: Option<String>,
+}
+
+impl Observer<u8, ()> for DtCheck {
+    fn enter(&mut self, _path: &[usize], _state: &State<u8, ()>) {
+        self.given.push(0.0);
+    }
+
+    fn update(&mut self, _path: &[usize], dt: f64) {
+        *self.given.last_mut().unwrap() = dt;
+    }
+
+    fn exit(&mut self, path: &[usize], _status: Status, dt: f64) {
+        let given = self.given.pop().unwrap();
+        if self.error.is_some() {
+            return;
+        }
+        if !dt.is_finite() || dt < 0.0 {
+            self.error = Some(format!("{:?} returned invalid delta time {}", path, dt));
+        } else if dt > given {
+            self.error = Some(format!(
+                "{:?} returned delta time {} exceeding the given {}",
+                path, dt, given
+            ));
+        }
+    }
+}
+
+fn seconds(u: &mut Unstructured<'_>) -> Result<f64> {
+    Ok(f64::from(u.int_in_range(0..=8u8)?) * 0.25)
+}
+
+fn button(u: &mut Unstructured<'_>) -> Result<Button> {
+    Ok(*u.choose(&[
+        Button::Keyboard(Key::A),
+        Button::Keyboard(Key::B),
+        Button::Mouse(MouseButton::Left),
+    ])?)
+}
+
+fn list(u: &mut Unstructured<'_>, min: u8, depth: u32) -> Result<Vec<Behavior<u8>>> {
+    let n = u.int_in_range(min..=4)?;
+    (0..n).map(|_| behavior(u, depth)).collect()
+}
+
+fn child(u: &mut Unstructured<'_>, depth: u32) -> Result<Box<Behavior<u8>>> {
+    Ok(Box::new(behavior(u, depth)?))
+}
+
+fn behavior(u: &mut Unstructured<'_>, depth: u32) -> Result<Behavior<u8>> {
+    let kinds = if depth >= MAX_DEPTH { 5 } else { 14 };
+    let depth = depth + 1;
+    Ok(match u.int_in_range(0..=kinds - 1)? {
+        0 => Wait(seconds(u)?),
+        1 => WaitForever,
+        2 => WaitForPressed(button(u)?),
+        3 => WaitForReleased(button(u)?),
+        4 => Behavior::Action(u.arbitrary()?),
+        5 => Fail(child(u, depth)?),
+        6 => AlwaysSucceed(child(u, depth)?),
+        7 => If(child(u, depth)?, child(u, depth)?, child(u, depth)?),
+        8 => {
+            let condition = child(u, depth)?;
+            While(condition, list(u, 1, depth)?)
+        }
+        9 => Select(list(u, 1, depth)?),
+        10 => Sequence(list(u, 1, depth)?),
+        11 => WhenAll(list(u, 0, depth)?),
+        12 => WhenAny(list(u, 0, depth)?),
+        _ => After(list(u, 0, depth)?),
+    })
+}
+
+fn event(u: &mut Unstructured<'_>) -> Result<RecordedEvent> {
+    Ok(match u.int_in_range(0..=3u8)? {
+        0 | 1 => RecordedEvent::Loop(Loop::Update(UpdateArgs { dt: seconds(u)? })),
+        2 => RecordedEvent::Input(Input::Button(ButtonArgs {
+            state: if u.arbitrary()? {
+                ButtonState::Press
+            } else {
+                ButtonState::Release
+            },
+            button: button(u)?,
+            scancode: None,
+        })),
+        _ => RecordedEvent::Custom,
+    })
+}
+
+impl<'a> Arbitrary<'a> for FuzzCase {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<FuzzCase> {
+        let behavior = behavior(u, 0)?;
+        let n = u.int_in_range(0..=32u8)?;
+        let events = (0..n).map(|_| event(u)).collect::<Result<_>>()?;
+        Ok(FuzzCase { behavior, events })
+    }
+}
+
+impl FuzzCase {
+    /// Runs an action with a fixed rule: action `a` returns
+    /// `Running`, `Success` or `Failure` for `a % 3` equal to 0, 1 or 2,
+    /// leaving 0, 1/2 or all of the delta time for `a / 3 % 3` equal to 0, 1 or 2.
+    pub fn run_action<E>(args: ActionArgs<E, u8, ()>) -> (Status, f64) {
+        let a = *args.action;
+        let status = [Running, Success, Failure][usize::from(a % 3)];
+        (status, args.dt * f64::from(a / 3 % 3) / 2.0)
+    }
+
+    /// Runs the events until the behavior terminates
+    /// and checks that the delta time returned by every node is finite,
+    /// non-negative and does not exceed the delta time given to the node.
+    ///
+    /// Actions are run with `FuzzCase::run_action`.
+    ///
+    /// Panics are not caught, so they are reported by the test framework.
+    pub fn check(&self) -> std::result::Result<(), InvariantError> {
+        let mut state: State<u8, ()> = State::new(self.behavior.clone());
+        let mut observer = DtCheck {
+            given: vec![],
+            error: None,
+        };
+        for (i, recorded) in self.events.iter().enumerate() {
+            let e: Event = recorded.clone().into();
+            let (status, _) =
+                state.event_with_observer(&e, &mut FuzzCase::run_action, &mut observer);
+            if let Some(message) = observer.error.take() {
+                return Err(InvariantError { event: i, message });
+            }
+            if status != Running {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
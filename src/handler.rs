@@ -0,0 +1,162 @@
+use std::any::Any;
+
+use crate::{ActionArgs, Status};
+
+/// An action state of any type, so every action can use its own type.
+///
+/// Use `ActionArgs::state_mut` or `ActionArgs::state_or_insert_with`
+/// to get the state with its type.
+/// States of this type can not be cloned or serialized.
+///
+/// ```
+/// use ai_behavior::{Action, ActionArgs, AnyState, Running, State, Status, Success, WhenAll};
+/// use input::{Event, UpdateArgs};
+///
+/// #[derive(Clone)]
+/// enum Npc {
+///     Walk(f64),
+///     Speak(&'static str),
+/// }
+///
+/// fn npc(mut args: ActionArgs<Event, Npc, AnyState>) -> (Status, f64) {
+///     match *args.action {
+///         Npc::Walk(distance) => {
+///             let dt = args.dt;
+///             let walked = args.state_or_insert_with(|| 0.0);
+///             *walked += dt;
+///             if *walked >= distance {
+///                 (Success, *walked - distance)
+///             } else {
+///                 (Running, 0.0)
+///             }
+///         }
+///         Npc::Speak(text) => {
+///             let dt = args.dt;
+///             let letters = args.state_or_insert_with(|| text.chars());
+///             match letters.next() {
+///                 Some(_) => (Running, 0.0),
+///                 None => (Success, dt),
+///             }
+///         }
+///     }
+/// }
+///
+/// let mut state = State::new(WhenAll(vec![Action(Npc::Walk(2.0)), Action(Npc::Speak("hi"))]));
+/// let e: Event = UpdateArgs { dt: 1.0 }.into();
+/// for _ in 0..2 {
+///     assert_eq!(state.event(&e, &mut npc), (Running, 0.0));
+/// }
+/// assert_eq!(state.event(&e, &mut npc), (Success, 1.0));
+/// ```
+pub type AnyState = Box<dyn Any>;
+
+impl<'a, E, A> ActionArgs<'a, E, A, AnyState> {
+    /// Returns the state, if it has type `T`.
+    pub fn state_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.state.as_mut().and_then(|state| state.downcast_mut())
+    }
+
+    /// Returns the state, replacing it with the value from the closure
+    /// if there is no state of type `T`.
+    pub fn state_or_insert_with<T: Any, F: FnOnce() -> T>(&mut self, f: F) -> &mut T {
+        if self.state_mut::<T>().is_none() {
+            *self.state = Some(Box::new(f()));
+        }
+        self.state_mut().unwrap()
+    }
+}
+
+/// Runs actions, as an alternative to a closure with one big `match`.
+///
+/// Closures `FnMut(ActionArgs<E, A, S>) -> (Status, f64)` are handlers
+/// that only implement `tick`, so they can be passed to `State::handle`.
+///
+/// Actions with different kinds of state can use `AnyState`,
+/// or an enum for `S`, created by `start`:
+///
+/// ```
+/// use ai_behavior::{Action, ActionArgs, ActionHandler, Running, State, Status, Success};
+/// use input::{Event, UpdateArgs};
+///
+/// #[derive(Clone)]
+/// enum Npc {
+///     Walk(f64),
+///     Speak(&'static str),
+/// }
+///
+/// enum Progress {
+///     Walked(f64),
+///     Letters(usize),
+/// }
+///
+/// struct Handler;
+///
+/// impl ActionHandler<Event, Npc, Progress> for Handler {
+///     fn start(&mut self, action: &Npc) -> Option<Progress> {
+///         Some(match *action {
+///             Npc::Walk(_) => Progress::Walked(0.0),
+///             Npc::Speak(_) => Progress::Letters(0),
+///         })
+///     }
+///
+///     fn tick(&mut self, args: ActionArgs<Event, Npc, Progress>) -> (Status, f64) {
+///         match (args.action, args.state.as_mut()) {
+///             (&Npc::Walk(distance), Some(Progress::Walked(walked))) => {
+///                 *walked += args.dt;
+///                 if *walked >= distance {
+///                     (Success, *walked - distance)
+///                 } else {
+///                     (Running, 0.0)
+///                 }
+///             }
+///             (&Npc::Speak(text), Some(Progress::Letters(n))) => {
+///                 *n += 1;
+///                 if *n >= text.len() {
+///                     (Success, args.dt)
+///                 } else {
+///                     (Running, 0.0)
+///                 }
+///             }
+///             _ => unreachable!(),
+///         }
+///     }
+/// }
+///
+/// let mut state = State::new(Action(Npc::Walk(2.0)));
+/// let e: Event = UpdateArgs { dt: 2.5 }.into();
+/// assert_eq!(state.handle(&e, &mut Handler), (Success, 0.5));
+/// ```
+pub trait ActionHandler<E, A, S> {
+    /// Creates the state of an action when it starts,
+    /// before its first `tick`.
+    ///
+    /// Called on the first tick every time the action runs,
+    /// even if it returns `None`, which it does by default.
+    /// A returned state replaces the state of the action,
+    /// while `None` keeps it, e.g. a state set before the run
+    /// or left by the last run of a terminated action.
+    fn start(&mut self, _action: &A) -> Option<S> {
+        None
+    }
+
+    /// Updates a running action like the closure passed to `State::event`,
+    /// returning the status and remaining delta time.
+    fn tick(&mut self, args: ActionArgs<E, A, S>) -> (Status, f64);
+
+    /// Called when a running action is stopped because its parent terminated,
+    /// with the state of the action.
+    ///
+    /// Actions that did not start yet are not halted.
+    ///
+    /// Does nothing by default.
+    fn halt(&mut self, _action: &A, _state: Option<S>) {}
+}
+
+impl<E, A, S, F> ActionHandler<E, A, S> for F
+where
+    F: FnMut(ActionArgs<E, A, S>) -> (Status, f64),
+{
+    fn tick(&mut self, args: ActionArgs<E, A, S>) -> (Status, f64) {
+        self(args)
+    }
+}
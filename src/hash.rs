@@ -0,0 +1,378 @@
+use std::error::Error;
+use std::fmt;
+
+use serde::ser::{self, Serialize};
+
+use crate::Behavior;
+
+// Feeds the serialized data into FNV-1a, with a tag before every value
+// and integers in little endian, so the hash only depends on the data model
+// of serde and is the same on every platform.
+struct Fnv(u64);
+
+// Tags of the values.
+const BOOL: u8 = 0;
+const INT: u8 = 1;
+const UINT: u8 = 2;
+const FLOAT: u8 = 3;
+const CHAR: u8 = 4;
+const STR: u8 = 5;
+const BYTES: u8 = 6;
+const NONE: u8 = 7;
+const SOME: u8 = 8;
+const UNIT: u8 = 9;
+const VARIANT: u8 = 10;
+const BEGIN: u8 = 11;
+const END: u8 = 12;
+
+impl Fnv {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= u64::from(b);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn tag(&mut self, tag: u8) {
+        self.write(&[tag]);
+    }
+
+    fn int(&mut self, v: i128) {
+        self.tag(INT);
+        self.write(&v.to_le_bytes());
+    }
+
+    fn uint(&mut self, v: u128) {
+        self.tag(UINT);
+        self.write(&v.to_le_bytes());
+    }
+
+    fn float(&mut self, v: f64) {
+        self.tag(FLOAT);
+        self.write(&v.to_bits().to_le_bytes());
+    }
+
+    fn bytes(&mut self, tag: u8, v: &[u8]) {
+        self.tag(tag);
+        self.write(&(v.len() as u64).to_le_bytes());
+        self.write(v);
+    }
+
+    fn variant(&mut self, index: u32) {
+        self.tag(VARIANT);
+        self.write(&index.to_le_bytes());
+    }
+}
+
+// Raised by `Serialize` implementations that fail.
+#[derive(Debug)]
+struct HashError(String);
+
+impl fmt::Display for HashError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for HashError {}
+
+impl ser::Error for HashError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        HashError(msg.to_string())
+    }
+}
+
+type Result = std::result::Result<(), HashError>;
+
+impl ser::Serializer for &mut Fnv {
+    type Ok = ();
+    type Error = HashError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result {
+        self.tag(BOOL);
+        self.write(&[v as u8]);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result {
+        self.serialize_i128(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result {
+        self.serialize_i128(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result {
+        self.serialize_i128(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result {
+        self.serialize_i128(v.into())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result {
+        self.int(v);
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result {
+        self.serialize_u128(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result {
+        self.serialize_u128(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result {
+        self.serialize_u128(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result {
+        self.serialize_u128(v.into())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result {
+        self.uint(v);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result {
+        self.float(v);
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result {
+        self.tag(CHAR);
+        self.write(&u32::from(v).to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result {
+        self.bytes(STR, v.as_bytes());
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result {
+        self.bytes(BYTES, v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result {
+        self.tag(NONE);
+        Ok(())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result {
+        self.tag(SOME);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result {
+        self.tag(UNIT);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        index: u32,
+        _variant: &'static str,
+    ) -> Result {
+        self.variant(index);
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result {
+        self.variant(index);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> std::result::Result<Self, HashError> {
+        self.tag(BEGIN);
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, len: usize) -> std::result::Result<Self, HashError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> std::result::Result<Self, HashError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        index: u32,
+        _variant: &'static str,
+        len: usize,
+    ) -> std::result::Result<Self, HashError> {
+        self.variant(index);
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> std::result::Result<Self, HashError> {
+        self.serialize_seq(len)
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> std::result::Result<Self, HashError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> std::result::Result<Self, HashError> {
+        self.serialize_tuple_variant(name, index, variant, len)
+    }
+}
+
+impl ser::SerializeSeq for &mut Fnv {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result {
+        self.tag(END);
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for &mut Fnv {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for &mut Fnv {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleVariant for &mut Fnv {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeMap for &mut Fnv {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeStruct for &mut Fnv {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, _key: &'static str, value: &T) -> Result {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeStructVariant for &mut Fnv {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, _key: &'static str, value: &T) -> Result {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl<A: Serialize> Behavior<A> {
+    /// Computes a hash of the behavior tree that is stable
+    /// between runs, platforms and releases.
+    ///
+    /// The hash is computed from the serialized behavior,
+    /// so actions are hashed by their `Serialize` implementation.
+    /// If serializing an action fails, the hash covers the data before it.
+    pub fn tree_hash(&self) -> u64 {
+        let mut h = Fnv(0xcbf2_9ce4_8422_2325);
+        let _ = self.serialize(&mut h);
+        h.0
+    }
+}
@@ -60,18 +60,69 @@
 //! ])
 //! ```
 
+#[cfg(feature = "fuzz")]
+extern crate arbitrary;
 extern crate input;
 #[macro_use]
 extern crate serde_derive;
 extern crate serde;
+#[cfg(feature = "schema")]
+extern crate serde_json;
 
 pub use behavior::Behavior::{
     self, Action, After, AlwaysSucceed, Fail, If, Select, Sequence, Wait, WaitForPressed,
     WaitForReleased, WaitForever, WhenAll, WhenAny, While,
 };
+pub use builder::Builder;
+pub use coverage::{Coverage, CoverageReport, NodeCoverage, Outcomes};
+pub use diff::{TreeChange, TreeDiff};
+pub use failure::{FailureReason, Reason};
+pub use future::ActionFuture;
+#[cfg(feature = "fuzz")]
+pub use fuzz::{FuzzCase, InvariantError};
+pub use handler::{ActionHandler, AnyState};
+#[cfg(feature = "mock")]
+pub use mock::MockActions;
+pub use observer::{Observer, Trace, TraceEvent};
+pub use profile::{NodeProfile, Profiler};
+pub use replay::{Frame, RecordedEvent, Recording, ReplayError, REPLAY_EVENT};
+pub use save::{Restored, Save, SaveHeader, SAVE_VERSION};
+#[cfg(feature = "schema")]
+pub use schema::{behavior_schema, JSON_SCHEMA_DIALECT};
+pub use snapshot::{Snapshot, SnapshotError};
 pub use state::{ActionArgs, State, RUNNING};
 pub use status::Status::{self, Failure, Running, Success};
+pub use text::ParseError;
+pub use validate::{Stats, ValidationError};
+pub use visit::{MapError, Visitor};
 
+mod ascii;
 mod behavior;
+mod builder;
+mod coverage;
+mod diff;
+mod dot;
+mod failure;
+mod future;
+#[cfg(feature = "fuzz")]
+mod fuzz;
+mod handler;
+mod hash;
+mod macros;
+mod mermaid;
+#[cfg(feature = "mock")]
+mod mock;
+mod observer;
+mod optimize;
+mod profile;
+mod reload;
+mod replay;
+mod save;
+#[cfg(feature = "schema")]
+mod schema;
+mod snapshot;
 mod state;
 mod status;
+mod text;
+mod validate;
+mod visit;
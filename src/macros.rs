@@ -0,0 +1,168 @@
+/// Creates a behavior from a declarative description.
+///
+/// Leaf behaviors are written as `wait 1.0`, `wait_forever`,
+/// `wait_for_pressed button`, `wait_for_released button` and `action a`,
+/// separated by `;`.
+/// An existing behavior can be inserted with `behavior b`.
+///
+/// Composite behaviors take a block of behaviors:
+/// `select { .. }`, `sequence { .. }`, `when_all { .. }`,
+/// `when_any { .. }` and `after { .. }`.
+///
+/// Decorators take a block with a single behavior:
+/// `fail { .. }` and `always_succeed { .. }`.
+///
+/// `if { condition } { success } else { failure }`
+/// and `while { condition } { body.. }` work like `If` and `While`.
+///
+/// `select`, `sequence` and the body of `while` can not be empty,
+/// since their states need a first behavior;
+/// writing them with an empty block is a compile error.
+///
+/// Every behavior in a block takes one level of macro recursion,
+/// so a block with more than about 120 behaviors exceeds the default
+/// recursion limit. Raise it with `#![recursion_limit = "256"]`
+/// in the crate using the macro, or build the list as a `Vec`
+/// and insert it with `behavior b`.
+///
+/// ```
+/// #[macro_use]
+/// extern crate ai_behavior;
+///
+/// use ai_behavior::{Action, Sequence, Wait};
+///
+/// # fn main() {
+/// let b = behavior! {
+///     sequence {
+///         wait 1.0;
+///         action "jump";
+///     }
+/// };
+/// assert!(b == Sequence(vec![Wait(1.0), Action("jump")]));
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use]
+/// # extern crate ai_behavior;
+/// # fn main() {
+/// let b: ai_behavior::Behavior<()> = behavior! { sequence { } };
+/// # }
+/// ```
+#[macro_export]
+macro_rules! behavior {
+    // Returns the parsed behaviors as a list or a single behavior.
+    (@done list [$($acc:expr,)*]) => { vec![$($acc),*] };
+    (@done one [$acc:expr,]) => { $acc };
+
+    (@parse $k:ident [$($acc:expr,)*]) => {
+        $crate::behavior!(@done $k [$($acc,)*])
+    };
+    (@parse $k:ident [$($acc:expr,)*] ; $($rest:tt)*) => {
+        $crate::behavior!(@parse $k [$($acc,)*] $($rest)*)
+    };
+
+    // Leaves.
+    (@parse $k:ident [$($acc:expr,)*] wait_forever $($rest:tt)*) => {
+        $crate::behavior!(@parse $k [$($acc,)* $crate::Behavior::WaitForever,] $($rest)*)
+    };
+    (@parse $k:ident [$($acc:expr,)*] wait_for_pressed $e:expr ; $($rest:tt)*) => {
+        $crate::behavior!(@parse $k [$($acc,)* $crate::Behavior::WaitForPressed($e),] $($rest)*)
+    };
+    (@parse $k:ident [$($acc:expr,)*] wait_for_pressed $e:expr) => {
+        $crate::behavior!(@parse $k [$($acc,)* $crate::Behavior::WaitForPressed($e),])
+    };
+    (@parse $k:ident [$($acc:expr,)*] wait_for_released $e:expr ; $($rest:tt)*) => {
+        $crate::behavior!(@parse $k [$($acc,)* $crate::Behavior::WaitForReleased($e),] $($rest)*)
+    };
+    (@parse $k:ident [$($acc:expr,)*] wait_for_released $e:expr) => {
+        $crate::behavior!(@parse $k [$($acc,)* $crate::Behavior::WaitForReleased($e),])
+    };
+    (@parse $k:ident [$($acc:expr,)*] wait $e:expr ; $($rest:tt)*) => {
+        $crate::behavior!(@parse $k [$($acc,)* $crate::Behavior::Wait($e),] $($rest)*)
+    };
+    (@parse $k:ident [$($acc:expr,)*] wait $e:expr) => {
+        $crate::behavior!(@parse $k [$($acc,)* $crate::Behavior::Wait($e),])
+    };
+    (@parse $k:ident [$($acc:expr,)*] action $e:expr ; $($rest:tt)*) => {
+        $crate::behavior!(@parse $k [$($acc,)* $crate::Behavior::Action($e),] $($rest)*)
+    };
+    (@parse $k:ident [$($acc:expr,)*] action $e:expr) => {
+        $crate::behavior!(@parse $k [$($acc,)* $crate::Behavior::Action($e),])
+    };
+    (@parse $k:ident [$($acc:expr,)*] behavior $e:expr ; $($rest:tt)*) => {
+        $crate::behavior!(@parse $k [$($acc,)* $e,] $($rest)*)
+    };
+    (@parse $k:ident [$($acc:expr,)*] behavior $e:expr) => {
+        $crate::behavior!(@parse $k [$($acc,)* $e,])
+    };
+
+    // Decorators.
+    (@parse $k:ident [$($acc:expr,)*] fail { $($b:tt)* } $($rest:tt)*) => {
+        $crate::behavior!(@parse $k [$($acc,)*
+            $crate::Behavior::Fail(Box::new($crate::behavior!(@parse one [] $($b)*))),
+        ] $($rest)*)
+    };
+    (@parse $k:ident [$($acc:expr,)*] always_succeed { $($b:tt)* } $($rest:tt)*) => {
+        $crate::behavior!(@parse $k [$($acc,)*
+            $crate::Behavior::AlwaysSucceed(Box::new($crate::behavior!(@parse one [] $($b)*))),
+        ] $($rest)*)
+    };
+
+    // Composites.
+    (@parse $k:ident [$($acc:expr,)*] select { } $($rest:tt)*) => {
+        compile_error!("`select` needs at least one behavior")
+    };
+    (@parse $k:ident [$($acc:expr,)*] sequence { } $($rest:tt)*) => {
+        compile_error!("`sequence` needs at least one behavior")
+    };
+    (@parse $k:ident [$($acc:expr,)*] while { $($c:tt)* } { } $($rest:tt)*) => {
+        compile_error!("the body of `while` needs at least one behavior")
+    };
+    (@parse $k:ident [$($acc:expr,)*] select { $($b:tt)* } $($rest:tt)*) => {
+        $crate::behavior!(@parse $k [$($acc,)*
+            $crate::Behavior::Select($crate::behavior!(@parse list [] $($b)*)),
+        ] $($rest)*)
+    };
+    (@parse $k:ident [$($acc:expr,)*] sequence { $($b:tt)* } $($rest:tt)*) => {
+        $crate::behavior!(@parse $k [$($acc,)*
+            $crate::Behavior::Sequence($crate::behavior!(@parse list [] $($b)*)),
+        ] $($rest)*)
+    };
+    (@parse $k:ident [$($acc:expr,)*] when_all { $($b:tt)* } $($rest:tt)*) => {
+        $crate::behavior!(@parse $k [$($acc,)*
+            $crate::Behavior::WhenAll($crate::behavior!(@parse list [] $($b)*)),
+        ] $($rest)*)
+    };
+    (@parse $k:ident [$($acc:expr,)*] when_any { $($b:tt)* } $($rest:tt)*) => {
+        $crate::behavior!(@parse $k [$($acc,)*
+            $crate::Behavior::WhenAny($crate::behavior!(@parse list [] $($b)*)),
+        ] $($rest)*)
+    };
+    (@parse $k:ident [$($acc:expr,)*] after { $($b:tt)* } $($rest:tt)*) => {
+        $crate::behavior!(@parse $k [$($acc,)*
+            $crate::Behavior::After($crate::behavior!(@parse list [] $($b)*)),
+        ] $($rest)*)
+    };
+    (@parse $k:ident [$($acc:expr,)*]
+        if { $($c:tt)* } { $($s:tt)* } else { $($f:tt)* } $($rest:tt)*
+    ) => {
+        $crate::behavior!(@parse $k [$($acc,)*
+            $crate::Behavior::If(
+                Box::new($crate::behavior!(@parse one [] $($c)*)),
+                Box::new($crate::behavior!(@parse one [] $($s)*)),
+                Box::new($crate::behavior!(@parse one [] $($f)*)),
+            ),
+        ] $($rest)*)
+    };
+    (@parse $k:ident [$($acc:expr,)*] while { $($c:tt)* } { $($b:tt)* } $($rest:tt)*) => {
+        $crate::behavior!(@parse $k [$($acc,)*
+            $crate::Behavior::While(
+                Box::new($crate::behavior!(@parse one [] $($c)*)),
+                $crate::behavior!(@parse list [] $($b)*),
+            ),
+        ] $($rest)*)
+    };
+
+    ($($t:tt)+) => { $crate::behavior!(@parse one [] $($t)+) };
+}
@@ -0,0 +1,52 @@
+use std::fmt::Write;
+
+use crate::Behavior;
+
+fn write<A, F>(out: &mut String, behavior: &Behavior<A>, next: &mut usize, action: &mut F) -> usize
+where
+    F: FnMut(&A) -> String,
+{
+    let id = *next;
+    *next += 1;
+    let _ = writeln!(
+        out,
+        "    n{}[\"{}\"]",
+        id,
+        escape(&behavior.label(&mut *action))
+    );
+    for (role, b) in behavior.children() {
+        let child = write(out, b, next, action);
+        let _ = match role {
+            Some(role) => writeln!(out, "    n{} -->|{}| n{}", id, role, child),
+            None => writeln!(out, "    n{} --> n{}", id, child),
+        };
+    }
+    id
+}
+
+// Escapes characters that end a label, as entity codes,
+// and breaks lines with `<br>`.
+fn escape(label: &str) -> String {
+    label
+        .replace('#', "#35;")
+        .replace('"', "#quot;")
+        .replace('[', "#91;")
+        .replace(']', "#93;")
+        .replace('|', "#124;")
+        .replace('\r', "")
+        .replace('\n', "<br>")
+}
+
+impl<A> Behavior<A> {
+    /// Exports the behavior tree as a Mermaid flowchart.
+    ///
+    /// The closure is used to label actions.
+    pub fn to_mermaid<F>(&self, mut action: F) -> String
+    where
+        F: FnMut(&A) -> String,
+    {
+        let mut out = String::from("flowchart TD\n");
+        write(&mut out, self, &mut 0, &mut action);
+        out
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::VecDeque;
+
+use input::{Event, UpdateArgs};
+
+use crate::{ActionArgs, Running, State, Status};
+
+#[derive(Clone, Debug)]
+struct MockAction<A> {
+    action: A,
+    responses: VecDeque<(Status, f64)>,
+    calls: usize,
+}
+
+/// A scriptable action handler for unit-testing behavior trees.
+///
+/// Requires the `mock` feature, e.g. in `[dev-dependencies]`.
+///
+/// Each action has a queue of responses, which are returned in order
+/// every time the action is called.
+/// The remaining delta time of a response is capped by the delta time
+/// given to the action, so an action never returns more time than it got.
+/// When the queue is empty, the action keeps running.
+///
+/// ```
+/// use ai_behavior::{Action, MockActions, Sequence, State, Success};
+///
+/// let mut mock = MockActions::new();
+/// mock.respond("jump", Success, 0.0);
+/// let mut state = State::new(Sequence(vec![Action("jump"), Action("land")]));
+/// mock.advance(&mut state, 0.5, 2);
+/// mock.assert_calls(&"jump", 1);
+/// mock.assert_calls(&"land", 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MockActions<A> {
+    actions: Vec<MockAction<A>>,
+}
+
+impl<A: Clone + PartialEq> Default for MockActions<A> {
+    fn default() -> MockActions<A> {
+        MockActions::new()
+    }
+}
+
+impl<A: Clone + PartialEq> MockActions<A> {
+    /// Creates a handler where all actions keep running.
+    pub fn new() -> MockActions<A> {
+        MockActions { actions: vec![] }
+    }
+
+    fn get(&mut self, action: &A) -> &mut MockAction<A> {
+        match self.actions.iter().position(|a| a.action == *action) {
+            Some(i) => &mut self.actions[i],
+            None => {
+                self.actions.push(MockAction {
+                    action: action.clone(),
+                    responses: VecDeque::new(),
+                    calls: 0,
+                });
+                self.actions.last_mut().unwrap()
+            }
+        }
+    }
+
+    /// Adds a response to the queue of an action,
+    /// with the status and remaining delta time to return.
+    pub fn respond(&mut self, action: A, status: Status, dt: f64) -> &mut Self {
+        self.get(&action).responses.push_back((status, dt));
+        self
+    }
+
+    /// Returns the number of times an action was called.
+    pub fn calls(&self, action: &A) -> usize {
+        self.actions
+            .iter()
+            .find(|a| a.action == *action)
+            .map_or(0, |a| a.calls)
+    }
+
+    /// Panics if an action was not called the expected number of times.
+    pub fn assert_calls(&self, action: &A, expected: usize)
+    where
+        A: std::fmt::Debug,
+    {
+        let calls = self.calls(action);
+        assert!(
+            calls == expected,
+            "Expected {:?} to be called {} times, but it was called {} times",
+            action,
+            expected,
+            calls
+        );
+    }
+
+    /// Handles an action, for use as the closure passed to `State::event`.
+    pub fn handle<E, S>(&mut self, args: ActionArgs<E, A, S>) -> (Status, f64) {
+        let action = self.get(args.action);
+        action.calls += 1;
+        match action.responses.pop_front() {
+            Some((status, dt)) => (status, args.dt.min(dt)),
+            None => (Running, 0.0),
+        }
+    }
+
+    /// Updates the state with delta time `dt`.
+    pub fn update(&mut self, state: &mut State<A, ()>, dt: f64) -> (Status, f64) {
+        let e: Event = UpdateArgs { dt }.into();
+        state.event(&e, &mut |args| self.handle(args))
+    }
+
+    /// Advances time by a number of steps with delta time `dt`,
+    /// stopping early when the behavior terminates.
+    ///
+    /// Returns the last status.
+    pub fn advance(&mut self, state: &mut State<A, ()>, dt: f64, steps: usize) -> Status {
+        let mut status = Running;
+        for _ in 0..steps {
+            status = self.update(state, dt).0;
+            if status != Running {
+                break;
+            }
+        }
+        status
+    }
+}
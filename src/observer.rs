@@ -0,0 +1,81 @@
+use crate::{State, Status};
+
+/// Observes the execution of a behavior tree.
+///
+/// Nodes are identified by their path,
+/// using the same numbering as `State::active_paths`.
+///
+/// All methods do nothing by default.
+/// The unit type `()` is an observer that ignores everything,
+/// which is used by `State::event`.
+pub trait Observer<A, S> {
+    /// Whether the observer uses the paths passed to it.
+    ///
+    /// When `false`, the paths are not built while updating and are empty,
+    /// which saves pushing an index for every visited node.
+    const NEEDS_PATH: bool = true;
+
+    /// Called before a node is updated.
+    fn enter(&mut self, _path: &[usize], _state: &State<A, S>) {}
+    /// Called after `enter` when the event is an update,
+    /// with the delta time given to the node.
+    fn update(&mut self, _path: &[usize], _dt: f64) {}
+    /// Called after a node is updated,
+    /// with the returned status and remaining delta time.
+    fn exit(&mut self, _path: &[usize], _status: Status, _dt: f64) {}
+    /// Called when a running node is stopped
+    /// because its parent terminated.
+    fn abort(&mut self, _path: &[usize]) {}
+}
+
+impl<A, S> Observer<A, S> for () {
+    const NEEDS_PATH: bool = false;
+}
+
+/// Something that happened to a node while updating.
+#[derive(Clone, Deserialize, Serialize, PartialEq, Debug)]
+pub enum TraceEvent {
+    /// The node was entered.
+    Enter(Vec<usize>),
+    /// The node was exited with a status and remaining delta time.
+    Exit(Vec<usize>, Status, f64),
+    /// The node was aborted.
+    Abort(Vec<usize>),
+}
+
+/// An observer that records everything that happens, in order.
+#[derive(Clone, Default, Deserialize, Serialize, PartialEq, Debug)]
+pub struct Trace {
+    /// The recorded events.
+    pub events: Vec<TraceEvent>,
+}
+
+impl Trace {
+    /// Creates an empty trace.
+    pub fn new() -> Trace {
+        Trace { events: vec![] }
+    }
+
+    /// Returns the status transitions, skipping enter and abort events.
+    pub fn exits(&self) -> impl Iterator<Item = (&[usize], Status, f64)> {
+        self.events.iter().filter_map(|ev| match *ev {
+            TraceEvent::Exit(ref path, status, dt) => Some((&path[..], status, dt)),
+            _ => None,
+        })
+    }
+}
+
+impl<A, S> Observer<A, S> for Trace {
+    fn enter(&mut self, path: &[usize], _state: &State<A, S>) {
+        self.events.push(TraceEvent::Enter(path.to_vec()));
+    }
+
+    fn exit(&mut self, path: &[usize], status: Status, dt: f64) {
+        self.events
+            .push(TraceEvent::Exit(path.to_vec(), status, dt));
+    }
+
+    fn abort(&mut self, path: &[usize]) {
+        self.events.push(TraceEvent::Abort(path.to_vec()));
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{
+    After, AlwaysSucceed, Behavior, Fail, If, Select, Sequence, Wait, WhenAll, WhenAny, While,
+};
+
+// Appends a child to a sequence, flattening nested sequences
+// and dropping zero waits next to another wait.
+fn push_sequence<A>(seq: &mut Vec<Behavior<A>>, behavior: Behavior<A>) {
+    match behavior {
+        Sequence(all) if !all.is_empty() => {
+            for b in all {
+                push_sequence(seq, b);
+            }
+        }
+        Wait(dt) if dt == 0.0 && matches!(seq.last(), Some(Wait(_))) => {}
+        Wait(dt) if matches!(seq.last(), Some(&Wait(prev)) if prev == 0.0) => {
+            *seq.last_mut().unwrap() = Wait(dt);
+        }
+        b => seq.push(b),
+    }
+}
+
+// Returns the only child, or the composite otherwise.
+fn single<A, F>(mut all: Vec<Behavior<A>>, composite: F) -> Behavior<A>
+where
+    F: FnOnce(Vec<Behavior<A>>) -> Behavior<A>,
+{
+    if all.len() == 1 {
+        all.pop().unwrap()
+    } else {
+        composite(all)
+    }
+}
+
+fn optimize_all<A>(all: Vec<Behavior<A>>) -> Vec<Behavior<A>> {
+    all.into_iter().map(Behavior::optimize).collect()
+}
+
+impl<A> Behavior<A> {
+    /// Simplifies redundant structure without changing how the behavior runs.
+    ///
+    /// - `Sequence` inside `Sequence` and `Select` inside `Select` are flattened
+    /// - `Fail(Fail(x))` becomes `x`
+    /// - `AlwaysSucceed(AlwaysSucceed(x))` and `AlwaysSucceed(Fail(x))`
+    ///   become `AlwaysSucceed(x)`, removing any number of such layers
+    /// - `Select`, `Sequence`, `WhenAll`, `WhenAny` and `After`
+    ///   with a single child become the child
+    /// - `Wait(0.0)` next to another `Wait` in a sequence is removed
+    ///
+    /// Other zero waits are kept, because they keep running on input events
+    /// and delay the following behaviors until the next update.
+    ///
+    /// The optimized behavior returns the same status for every event,
+    /// the same remaining delta time when it terminates
+    /// and calls the same actions, in the same order,
+    /// provided that actions never return more delta time than they are given.
+    /// The delta time returned while running can differ,
+    /// e.g. `Sequence` returns none, while its only child may return some.
+    /// Paths of nodes change, so traces and profiles of the two differ.
+    pub fn optimize(self) -> Behavior<A> {
+        match self {
+            Fail(ev) => match ev.optimize() {
+                Fail(ev) => *ev,
+                ev => Fail(Box::new(ev)),
+            },
+            AlwaysSucceed(ev) => {
+                let mut ev = ev.optimize();
+                // The status of the child does not matter, only when it terminates.
+                while let AlwaysSucceed(inner) | Fail(inner) = ev {
+                    ev = *inner;
+                }
+                AlwaysSucceed(Box::new(ev))
+            }
+            If(condition, success, failure) => If(
+                Box::new(condition.optimize()),
+                Box::new(success.optimize()),
+                Box::new(failure.optimize()),
+            ),
+            While(ev, rep) => While(Box::new(ev.optimize()), optimize_all(rep)),
+            Sequence(all) => {
+                let mut seq = vec![];
+                for b in all {
+                    push_sequence(&mut seq, b.optimize());
+                }
+                single(seq, Sequence)
+            }
+            Select(all) => {
+                let mut sel = vec![];
+                for b in all {
+                    match b.optimize() {
+                        Select(inner) if !inner.is_empty() => sel.extend(inner),
+                        b => sel.push(b),
+                    }
+                }
+                single(sel, Select)
+            }
+            WhenAll(all) => single(optimize_all(all), WhenAll),
+            WhenAny(all) => single(optimize_all(all), WhenAny),
+            After(all) => single(optimize_all(all), After),
+            leaf => leaf,
+        }
+    }
+}
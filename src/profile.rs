@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::state::State::ActionState;
+use crate::{Failure, Observer, Running, State, Status, Success};
+
+/// Counters for a single node.
+#[derive(Copy, Clone, Default, Deserialize, Serialize, PartialEq, Debug)]
+pub struct NodeProfile {
+    /// Number of times the node was updated.
+    pub ticks: u64,
+    /// Number of times the action closure was called for this node.
+    pub actions: u64,
+    /// Wall-clock time spent inside the action closure.
+    pub time: Duration,
+    /// Number of times the node returned `Success`.
+    pub success: u64,
+    /// Number of times the node returned `Failure`.
+    pub failure: u64,
+    /// Number of times the node returned `Running`.
+    pub running: u64,
+}
+
+/// An observer that accumulates counters per node.
+///
+/// The same profiler can be passed to many states using the same behavior,
+/// to get the total cost of all agents.
+#[derive(Clone, Default, Debug)]
+pub struct Profiler {
+    /// The counters of every node that was updated, keyed by path.
+    pub nodes: BTreeMap<Vec<usize>, NodeProfile>,
+    // When each entered action started, `None` for other nodes.
+    started: Vec<Option<Instant>>,
+}
+
+impl Profiler {
+    /// Creates a new profiler.
+    pub fn new() -> Profiler {
+        Profiler {
+            nodes: BTreeMap::new(),
+            started: vec![],
+        }
+    }
+
+    /// Returns the counters of a node, if it was updated.
+    pub fn get(&self, path: &[usize]) -> Option<&NodeProfile> {
+        self.nodes.get(path)
+    }
+
+    /// Returns the time spent in actions of a node and all nodes below it.
+    pub fn subtree_time(&self, path: &[usize]) -> Duration {
+        self.nodes
+            .range(path.to_vec()..)
+            .take_while(|&(p, _)| p.starts_with(path))
+            .map(|(_, node)| node.time)
+            .sum()
+    }
+
+    /// Clears all counters.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+    }
+}
+
+impl<A, S> Observer<A, S> for Profiler {
+    fn enter(&mut self, path: &[usize], state: &State<A, S>) {
+        let node = self.nodes.entry(path.to_vec()).or_default();
+        node.ticks += 1;
+        self.started.push(match *state {
+            ActionState(..) => {
+                node.actions += 1;
+                Some(Instant::now())
+            }
+            _ => None,
+        });
+    }
+
+    fn exit(&mut self, path: &[usize], status: Status, _dt: f64) {
+        let started = self.started.pop().and_then(|started| started);
+        let node = self.nodes.entry(path.to_vec()).or_default();
+        if let Some(started) = started {
+            node.time += started.elapsed();
+        }
+        match status {
+            Success => node.success += 1,
+            Failure => node.failure += 1,
+            Running => node.running += 1,
+        }
+    }
+}
+
+/// Writes a report with one line per node.
+impl fmt::Display for Profiler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<16} {:>8} {:>8} {:>12} {:>12} {:>8} {:>8} {:>8}",
+            "path", "ticks", "actions", "time", "subtree", "success", "failure", "running"
+        )?;
+        for (path, node) in &self.nodes {
+            writeln!(
+                f,
+                "{:<16} {:>8} {:>8} {:>12} {:>12} {:>8} {:>8} {:>8}",
+                format!("{:?}", path),
+                node.ticks,
+                node.actions,
+                format!("{:?}", node.time),
+                format!("{:?}", self.subtree_time(path)),
+                node.success,
+                node.failure,
+                node.running
+            )?;
+        }
+        Ok(())
+    }
+}
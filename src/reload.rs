@@ -0,0 +1,173 @@
+use std::mem;
+
+use crate::state::State::{
+    self, ActionState, AfterState, AlwaysSucceedState, FailState, IfState, SelectState,
+    SequenceState, WaitForeverState, WaitState, WhenAllState, WhenAnyState, WhileState,
+};
+use crate::{
+    Action, ActionHandler, After, AlwaysSucceed, Behavior, Fail, Failure, If, Running, Select,
+    Sequence, Success, Wait, WhenAll, WhenAny, While,
+};
+
+// Maps the running state onto the new behavior,
+// creating a new state where the kind of node changed.
+// The states that are replaced are passed to `halt`.
+fn reload<A, S, H>(state: State<A, S>, behavior: Behavior<A>, halt: &mut H) -> State<A, S>
+where
+    A: Clone + PartialEq,
+    H: FnMut(State<A, S>),
+{
+    let cursor = |cur: Box<State<A, S>>, behavior: Behavior<A>, halt: &mut H| {
+        Box::new(reload(*cur, behavior, halt))
+    };
+    match (state, behavior) {
+        (ActionState(a, s, started), Action(b)) if a == b => ActionState(b, s, started),
+        (WaitState(_, t), Wait(dt)) => WaitState(dt, t.min(dt)),
+        (FailState(cur), Fail(ev)) => FailState(cursor(cur, *ev, halt)),
+        (AlwaysSucceedState(cur), AlwaysSucceed(ev)) => AlwaysSucceedState(cursor(cur, *ev, halt)),
+        (IfState(_, _, _, status, cur), If(condition, success, failure)) => {
+            let behavior = match status {
+                Running => (*condition).clone(),
+                Success => (*success).clone(),
+                Failure => (*failure).clone(),
+            };
+            IfState(
+                condition,
+                success,
+                failure,
+                status,
+                cursor(cur, behavior, halt),
+            )
+        }
+        // Terminated composites stay terminated when unchanged.
+        (SelectState(old, i, cur), Select(sel)) if i == sel.len() && old == sel => {
+            SelectState(sel, i, cur)
+        }
+        (SequenceState(old, i, cur), Sequence(seq)) if i == seq.len() && old == seq => {
+            SequenceState(seq, i, cur)
+        }
+        (SelectState(_, i, cur), Select(sel)) if i < sel.len() => {
+            let cur = cursor(cur, sel[i].clone(), halt);
+            SelectState(sel, i, cur)
+        }
+        (SequenceState(_, i, cur), Sequence(seq)) if i < seq.len() => {
+            let cur = cursor(cur, seq[i].clone(), halt);
+            SequenceState(seq, i, cur)
+        }
+        (WhileState(ev_cursor, _, i, cur), While(ev, rep)) if !rep.is_empty() => {
+            let ev_cursor = cursor(ev_cursor, *ev, halt);
+            let (i, cur) = if i < rep.len() {
+                (i, cursor(cur, rep[i].clone(), halt))
+            } else {
+                halt(*cur);
+                (0, Box::new(State::new(rep[0].clone())))
+            };
+            WhileState(ev_cursor, rep, i, cur)
+        }
+        (WhenAllState(old, cursors), WhenAll(all)) => {
+            let cursors = reload_parallel(&old, cursors, &all, halt);
+            WhenAllState(all, cursors)
+        }
+        (WhenAnyState(old, cursors), WhenAny(all)) => {
+            let cursors = reload_parallel(&old, cursors, &all, halt);
+            WhenAnyState(all, cursors)
+        }
+        (AfterState(i, cursors), After(seq)) => {
+            // Children that succeeded run again from the first one that changed,
+            // so the children still succeed in order.
+            let done = i;
+            let i = cursors
+                .iter()
+                .zip(&seq)
+                .take(done)
+                .position(|(cur, ev)| cur.behavior() != *ev)
+                .unwrap_or(done)
+                .min(seq.len());
+            let mut old = cursors.into_iter();
+            let cursors: Vec<_> = seq
+                .into_iter()
+                .enumerate()
+                .map(|(j, ev)| match old.next() {
+                    Some(cur) if j < i => cur,
+                    Some(cur) if j >= done => reload(cur, ev, halt),
+                    _ => State::new(ev),
+                })
+                .collect();
+            old.for_each(halt);
+            AfterState(i, cursors)
+        }
+        (state, behavior) => {
+            halt(state);
+            State::new(behavior)
+        }
+    }
+}
+
+// Terminated children stay terminated when unchanged.
+fn reload_parallel<A, S, H>(
+    old: &[Behavior<A>],
+    cursors: Vec<Option<State<A, S>>>,
+    all: &[Behavior<A>],
+    halt: &mut H,
+) -> Vec<Option<State<A, S>>>
+where
+    A: Clone + PartialEq,
+    H: FnMut(State<A, S>),
+{
+    let mut cursors = cursors.into_iter();
+    let reloaded = all
+        .iter()
+        .enumerate()
+        .map(|(j, ev)| match cursors.next() {
+            Some(Some(cur)) => Some(reload(cur, ev.clone(), halt)),
+            Some(None) if old.get(j) == Some(ev) => None,
+            _ => Some(State::new(ev.clone())),
+        })
+        .collect();
+    cursors.flatten().for_each(halt);
+    reloaded
+}
+
+impl<A: Clone + PartialEq, S> State<A, S> {
+    /// Replaces the behavior while keeping the progress of running nodes,
+    /// e.g. when the behavior was edited while the game is running.
+    ///
+    /// Running nodes are matched by path with nodes in the new behavior.
+    /// A node of the same kind keeps its progress:
+    ///
+    /// - `Wait` keeps the elapsed time, up to the new time to wait
+    /// - `Action` keeps the action state, if the action is equal
+    /// - composites keep the index of the running child, if it still exists
+    /// - children of `WhenAll` and `WhenAny` that terminated stay terminated,
+    ///   if they are unchanged
+    /// - `After` runs again from the first child that succeeded and changed
+    ///
+    /// Other nodes start over, together with their children.
+    /// A parallel node where every child terminated
+    /// terminates on the next event.
+    ///
+    /// Running actions that are replaced are not halted,
+    /// their state is dropped. Use `reload_with_handler` to halt them.
+    ///
+    /// # Panics
+    ///
+    /// Like `State::new`, panics when a node that starts over
+    /// is a `Select`, `Sequence` or `While` without children.
+    /// Use `Behavior::validate` to check the new behavior first.
+    pub fn reload(&mut self, behavior: Behavior<A>) {
+        let state = mem::replace(self, WaitForeverState);
+        *self = reload(state, behavior, &mut drop);
+    }
+
+    /// Replaces the behavior like `reload`,
+    /// halting the running actions that are replaced with the handler.
+    pub fn reload_with_handler<E, H>(&mut self, behavior: Behavior<A>, handler: &mut H)
+    where
+        H: ActionHandler<E, A, S>,
+    {
+        let state = mem::replace(self, WaitForeverState);
+        *self = reload(state, behavior, &mut |mut old: State<A, S>| {
+            old.halt(handler)
+        });
+    }
+}
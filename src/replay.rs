@@ -0,0 +1,187 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use input::event_id::EventId;
+use input::{Event, Input, Loop};
+
+use crate::state::State::ActionState;
+use crate::{ActionArgs, Behavior, Observer, State, Status, Trace, TraceEvent, RUNNING};
+
+/// An event stored in a recording.
+#[derive(Clone, Deserialize, Serialize, PartialEq, Debug)]
+pub enum RecordedEvent {
+    /// Input event, without time stamp.
+    Input(Input),
+    /// Event loop event.
+    Loop(Loop),
+    /// Custom event.
+    ///
+    /// The payload is not recorded,
+    /// because behaviors only react to custom events through actions,
+    /// and actions are not called when replaying.
+    Custom,
+}
+
+impl<'a> From<&'a Event> for RecordedEvent {
+    fn from(e: &'a Event) -> RecordedEvent {
+        match *e {
+            Event::Input(ref input, _) => RecordedEvent::Input(input.clone()),
+            Event::Loop(l) => RecordedEvent::Loop(l),
+            Event::Custom(..) => RecordedEvent::Custom,
+        }
+    }
+}
+
+impl From<RecordedEvent> for Event {
+    fn from(e: RecordedEvent) -> Event {
+        match e {
+            RecordedEvent::Input(input) => Event::Input(input, None),
+            RecordedEvent::Loop(l) => Event::Loop(l),
+            RecordedEvent::Custom => Event::Custom(REPLAY_EVENT, Arc::new(()), None),
+        }
+    }
+}
+
+/// The id of custom events created when replaying.
+pub const REPLAY_EVENT: EventId = EventId("ai_behavior/replay");
+
+/// Everything that happened during one event.
+#[derive(Clone, Deserialize, Serialize, PartialEq, Debug)]
+pub struct Frame {
+    /// The event.
+    pub event: RecordedEvent,
+    /// The status and remaining delta time returned by actions,
+    /// in the order they were called.
+    pub actions: Vec<(Status, f64)>,
+    /// The status transitions of every node.
+    pub trace: Vec<TraceEvent>,
+}
+
+/// Records events and action outcomes, such that they can be replayed
+/// without running the actions.
+#[derive(Clone, Default, Deserialize, Serialize, PartialEq, Debug)]
+pub struct Recording {
+    /// The recorded frames, one per event.
+    pub frames: Vec<Frame>,
+}
+
+/// Records the action outcomes and trace of one event.
+struct FrameObserver {
+    trace: Trace,
+    actions: Vec<(Status, f64)>,
+    // Whether each entered node is an action.
+    is_action: Vec<bool>,
+}
+
+impl<A, S> Observer<A, S> for FrameObserver {
+    fn enter(&mut self, path: &[usize], state: &State<A, S>) {
+        let is_action = matches!(*state, ActionState(..));
+        self.is_action.push(is_action);
+        Observer::<A, S>::enter(&mut self.trace, path, state);
+    }
+
+    fn exit(&mut self, path: &[usize], status: Status, dt: f64) {
+        if self.is_action.pop() == Some(true) {
+            self.actions.push((status, dt));
+        }
+        Observer::<A, S>::exit(&mut self.trace, path, status, dt);
+    }
+
+    fn abort(&mut self, path: &[usize]) {
+        Observer::<A, S>::abort(&mut self.trace, path);
+    }
+}
+
+/// Describes where a replay diverged from the recording.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ReplayError {
+    /// The index of the frame.
+    pub frame: usize,
+    /// The index in the trace of the frame.
+    pub index: usize,
+    /// The recorded transition, if any.
+    pub expected: Option<TraceEvent>,
+    /// The replayed transition, if any.
+    pub found: Option<TraceEvent>,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Replay diverged at frame {}, transition {}: expected {:?}, found {:?}",
+            self.frame, self.index, self.expected, self.found
+        )
+    }
+}
+
+impl Error for ReplayError {}
+
+impl Recording {
+    /// Creates an empty recording.
+    pub fn new() -> Recording {
+        Recording { frames: vec![] }
+    }
+
+    /// Updates the state like `State::event` and records what happened.
+    pub fn record<A, S, F>(
+        &mut self,
+        state: &mut State<A, S>,
+        e: &Event,
+        f: &mut F,
+    ) -> (Status, f64)
+    where
+        A: Clone,
+        F: FnMut(ActionArgs<Event, A, S>) -> (Status, f64),
+    {
+        let mut observer = FrameObserver {
+            trace: Trace::new(),
+            actions: vec![],
+            is_action: vec![],
+        };
+        let res = state.event_with_observer(e, f, &mut observer);
+        self.frames.push(Frame {
+            event: e.into(),
+            actions: observer.actions,
+            trace: observer.trace.events,
+        });
+        res
+    }
+
+    /// Replays the recording on a new state created from the behavior.
+    ///
+    /// Actions return the recorded outcomes instead of being executed.
+    /// Returns an error at the first transition that differs from the recording.
+    ///
+    /// Delta times are compared exactly, so a serialized recording
+    /// must use a format that round-trips floats without loss.
+    pub fn replay<A: Clone>(&self, behavior: Behavior<A>) -> Result<(), ReplayError> {
+        let mut state: State<A, ()> = State::new(behavior);
+        for (n, frame) in self.frames.iter().enumerate() {
+            let mut actions = frame.actions.iter();
+            let mut trace = Trace::new();
+            let e: Event = frame.event.clone().into();
+            state.event_with_observer(
+                &e,
+                // Calling more actions than recorded shows up in the trace.
+                &mut |_| actions.next().cloned().unwrap_or(RUNNING),
+                &mut trace,
+            );
+            let len = trace.events.len().max(frame.trace.len());
+            for i in 0..len {
+                let expected = frame.trace.get(i);
+                let found = trace.events.get(i);
+                if expected != found {
+                    return Err(ReplayError {
+                        frame: n,
+                        index: i,
+                        expected: expected.cloned(),
+                        found: found.cloned(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::state::State;
+use crate::Behavior;
+
+/// The version of the save format, stored in every `Save`.
+///
+/// It changes when the serialized layout of `State` changes.
+pub const SAVE_VERSION: u32 = 1;
+
+/// The version and tree hash of a save,
+/// which can be deserialized from a save without reading the state.
+#[derive(Copy, Clone, Deserialize, Serialize, PartialEq, Eq, Debug)]
+pub struct SaveHeader {
+    /// The version of the save format.
+    pub version: u32,
+    /// The `Behavior::tree_hash` of the behavior running when saved.
+    pub tree_hash: u64,
+}
+
+/// A serializable state with the format version and a hash of its behavior.
+///
+/// When the behavior changed since the state was saved,
+/// the state is migrated or restarted by `Save::load`.
+#[derive(Clone, Deserialize, Serialize, PartialEq, Debug)]
+pub struct Save<A, S> {
+    /// The version of the save format.
+    pub version: u32,
+    /// The `Behavior::tree_hash` of the behavior running when saved.
+    pub tree_hash: u64,
+    /// The saved state.
+    pub state: State<A, S>,
+}
+
+/// How `Save::load` got the state.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Restored {
+    /// The save was made with the current format and behavior.
+    Loaded,
+    /// The save was migrated.
+    Migrated,
+    /// The migration gave up, so the state started over.
+    Restarted,
+}
+
+impl<A: Clone + PartialEq + Serialize, S> Save<A, S> {
+    /// Saves the state of a behavior.
+    pub fn new(behavior: &Behavior<A>, state: State<A, S>) -> Save<A, S> {
+        Save {
+            version: SAVE_VERSION,
+            tree_hash: behavior.tree_hash(),
+            state,
+        }
+    }
+
+    /// Returns the version and tree hash.
+    pub fn header(&self) -> SaveHeader {
+        SaveHeader {
+            version: self.version,
+            tree_hash: self.tree_hash,
+        }
+    }
+
+    /// Loads the state for the current behavior from serialized data.
+    ///
+    /// The header is read first.
+    /// If the save has the current format version and tree hash,
+    /// the state is deserialized as it is.
+    /// Otherwise, the migration is called with the header, the data
+    /// and the current behavior, so it can read any format version.
+    /// It returns the upgraded state, or `None` to start over.
+    /// `Save::migrate` is the default migration.
+    ///
+    /// The data is read twice, so it is usually a parsed value,
+    /// e.g. `&serde_json::Value`.
+    ///
+    /// ```
+    /// use ai_behavior::{Action, Restored, Save, Sequence, State, Wait};
+    ///
+    /// let old = Sequence(vec![Wait(1.0), Action("jump".to_string())]);
+    /// let save: Save<String, ()> = Save::new(&old, State::new(old.clone()));
+    /// let data = serde_json::to_value(&save).unwrap();
+    ///
+    /// let new = Sequence(vec![Wait(2.0), Action("jump".to_string())]);
+    /// let (_state, restored) = Save::<String, ()>::load(&data, new, Save::migrate).unwrap();
+    /// assert_eq!(restored, Restored::Migrated);
+    /// ```
+    pub fn load<'de, D, F>(
+        data: D,
+        behavior: Behavior<A>,
+        migrate: F,
+    ) -> Result<(State<A, S>, Restored), D::Error>
+    where
+        A: Deserialize<'de>,
+        S: Deserialize<'de>,
+        D: Deserializer<'de> + Clone,
+        F: FnOnce(SaveHeader, D, &Behavior<A>) -> Result<Option<State<A, S>>, D::Error>,
+    {
+        let header = SaveHeader::deserialize(data.clone())?;
+        if header.version == SAVE_VERSION && header.tree_hash == behavior.tree_hash() {
+            let save = Save::deserialize(data)?;
+            return Ok((save.state, Restored::Loaded));
+        }
+        Ok(match migrate(header, data, &behavior)? {
+            Some(state) => (state, Restored::Migrated),
+            None => (State::new(behavior), Restored::Restarted),
+        })
+    }
+
+    /// A migration that reads saves of the current format version
+    /// and maps the state onto the new behavior with `State::reload`,
+    /// so the actions it replaces are not halted.
+    ///
+    /// Saves of other versions start over.
+    pub fn migrate<'de, D>(
+        header: SaveHeader,
+        data: D,
+        behavior: &Behavior<A>,
+    ) -> Result<Option<State<A, S>>, D::Error>
+    where
+        A: Deserialize<'de>,
+        S: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let mut state = match header.version {
+            SAVE_VERSION => Save::deserialize(data)?.state,
+            _ => return Ok(None),
+        };
+        state.reload(behavior.clone());
+        Ok(Some(state))
+    }
+}
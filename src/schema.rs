@@ -0,0 +1,142 @@
+use input::{Key, MouseButton};
+use serde_json::{json, Map, Value};
+
+/// The JSON Schema dialect used by `behavior_schema`.
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+fn behavior_ref() -> Value {
+    json!({ "$ref": "#/$defs/Behavior" })
+}
+
+// Matches the default representation of an enum variant with data,
+// which is an object with the variant name as the only key.
+fn variant(name: &str, schema: Value) -> Value {
+    let mut properties = Map::new();
+    properties.insert(name.into(), schema);
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": [name],
+        "additionalProperties": false,
+    })
+}
+
+fn list(min_items: usize) -> Value {
+    json!({
+        "type": "array",
+        "items": behavior_ref(),
+        "minItems": min_items,
+    })
+}
+
+// Collects the serialized names of unit variants.
+fn names<T, I>(values: I) -> Vec<Value>
+where
+    T: serde::Serialize,
+    I: IntoIterator<Item = T>,
+{
+    let mut names: Vec<Value> = vec![];
+    for value in values {
+        let name = serde_json::to_value(value).unwrap();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+fn keys() -> Vec<Value> {
+    // Key codes are ASCII or SDL scan codes with bit 30 set.
+    names((0..0x80).chain(0x4000_0039..0x4000_011B).map(Key::from))
+}
+
+fn mouse_buttons() -> Vec<Value> {
+    names((0..9).map(MouseButton::from))
+}
+
+fn hat_states() -> Vec<Value> {
+    use input::HatState::*;
+
+    names(vec![
+        Centered, Up, Right, Down, Left, RightUp, RightDown, LeftUp, LeftDown,
+    ])
+}
+
+/// Generates a JSON Schema for serialized `Behavior<A>`,
+/// given a schema for the action type `A`.
+///
+/// The schema matches the representation derived with Serde,
+/// e.g. `Wait(1.0)` is `{"Wait": 1.0}` and `WaitForever` is `"WaitForever"`.
+/// Composites that panic when empty, such as `Sequence`, require one item.
+///
+/// The action schema is stored in `$defs/Action`,
+/// so any references it contains must be absolute.
+pub fn behavior_schema(action: Value) -> Value {
+    let u8_schema = json!({ "type": "integer", "minimum": 0, "maximum": 255 });
+    let u32_schema = json!({ "type": "integer", "minimum": 0, "maximum": u32::MAX });
+    json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "title": "Behavior",
+        "$ref": "#/$defs/Behavior",
+        "$defs": {
+            "Action": action,
+            "Behavior": {
+                "oneOf": [
+                    variant("WaitForPressed", json!({ "$ref": "#/$defs/Button" })),
+                    variant("WaitForReleased", json!({ "$ref": "#/$defs/Button" })),
+                    variant("Wait", json!({ "type": "number" })),
+                    { "const": "WaitForever" },
+                    variant("Action", json!({ "$ref": "#/$defs/Action" })),
+                    variant("Fail", behavior_ref()),
+                    variant("AlwaysSucceed", behavior_ref()),
+                    variant("Select", list(1)),
+                    variant("If", json!({
+                        "type": "array",
+                        "prefixItems": [behavior_ref(), behavior_ref(), behavior_ref()],
+                        "items": false,
+                        "minItems": 3,
+                    })),
+                    variant("Sequence", list(1)),
+                    variant("While", json!({
+                        "type": "array",
+                        "prefixItems": [behavior_ref(), list(1)],
+                        "items": false,
+                        "minItems": 2,
+                    })),
+                    variant("WhenAll", list(0)),
+                    variant("WhenAny", list(0)),
+                    variant("After", list(0)),
+                ]
+            },
+            "Button": {
+                "oneOf": [
+                    variant("Keyboard", json!({ "$ref": "#/$defs/Key" })),
+                    variant("Mouse", json!({ "$ref": "#/$defs/MouseButton" })),
+                    variant("Controller", json!({ "$ref": "#/$defs/ControllerButton" })),
+                    variant("Hat", json!({ "$ref": "#/$defs/ControllerHat" })),
+                ]
+            },
+            "Key": { "enum": keys() },
+            "MouseButton": { "enum": mouse_buttons() },
+            "ControllerButton": {
+                "type": "object",
+                "properties": {
+                    "id": u32_schema,
+                    "button": u8_schema,
+                },
+                "required": ["id", "button"],
+                "additionalProperties": false,
+            },
+            "ControllerHat": {
+                "type": "object",
+                "properties": {
+                    "id": u32_schema,
+                    "state": { "enum": hat_states() },
+                    "which": u8_schema,
+                },
+                "required": ["id", "state", "which"],
+                "additionalProperties": false,
+            },
+        }
+    })
+}
@@ -0,0 +1,194 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::state::State::{
+    self, ActionState, AfterState, AlwaysSucceedState, FailState, IfState, SelectState,
+    SequenceState, WaitForPressedState, WaitForReleasedState, WaitForeverState, WaitState,
+    WhenAllState, WhenAnyState, WhileState,
+};
+use crate::{
+    Action, After, AlwaysSucceed, Behavior, Fail, Failure, If, Running, Select, Sequence, Status,
+    Success, Wait, WaitForPressed, WaitForReleased, WaitForever, WhenAll, WhenAny, While,
+};
+
+/// The progress of a state without the behavior,
+/// created by `State::snapshot`.
+///
+/// Snapshots store only indices, statuses, timers and action states,
+/// so the behavior is saved once, or identified by `Behavior::tree_hash`,
+/// instead of being copied into every composite of the state.
+#[derive(Clone, Deserialize, Serialize, PartialEq, Debug)]
+pub enum Snapshot<S> {
+    /// `WaitForPressed`, `WaitForReleased` or `WaitForever`.
+    Leaf,
+    /// An action with its state and whether it started.
+    Action(Option<S>, bool),
+    /// A wait with the elapsed time in seconds.
+    Wait(f64),
+    /// `Fail` or `AlwaysSucceed` with its child.
+    Decorator(Box<Snapshot<S>>),
+    /// An `If` with the status of the condition and the running child.
+    If(Status, Box<Snapshot<S>>),
+    /// A `Select` or `Sequence` with the index of the running child.
+    Composite(usize, Box<Snapshot<S>>),
+    /// A `While` with the condition,
+    /// the index in the body and the running child.
+    While(Box<Snapshot<S>>, usize, Box<Snapshot<S>>),
+    /// `WhenAll` or `WhenAny`, with `None` for terminated children.
+    Parallel(Vec<Option<Snapshot<S>>>),
+    /// An `After` with the index of the next child to terminate.
+    After(usize, Vec<Snapshot<S>>),
+}
+
+/// A snapshot that does not fit the behavior, returned by `State::from_snapshot`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SnapshotError {
+    /// The path of the node, as used by `State::active_paths`.
+    pub path: Vec<usize>,
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Snapshot does not match the behavior at {:?}", self.path)
+    }
+}
+
+impl Error for SnapshotError {}
+
+fn restore<A: Clone, S>(
+    behavior: Behavior<A>,
+    snapshot: Snapshot<S>,
+    path: &mut Vec<usize>,
+) -> Result<State<A, S>, SnapshotError> {
+    let mut child = |i: usize, behavior: Behavior<A>, snapshot: Snapshot<S>| {
+        path.push(i);
+        let res = restore(behavior, snapshot, path);
+        path.pop();
+        res.map(Box::new)
+    };
+    Ok(match (behavior, snapshot) {
+        (WaitForPressed(button), Snapshot::Leaf) => WaitForPressedState(button),
+        (WaitForReleased(button), Snapshot::Leaf) => WaitForReleasedState(button),
+        (WaitForever, Snapshot::Leaf) => WaitForeverState,
+        (Action(a), Snapshot::Action(s, started)) => ActionState(a, s, started),
+        (Wait(dt), Snapshot::Wait(t)) => WaitState(dt, t),
+        (Fail(ev), Snapshot::Decorator(cur)) => FailState(child(0, *ev, *cur)?),
+        (AlwaysSucceed(ev), Snapshot::Decorator(cur)) => AlwaysSucceedState(child(0, *ev, *cur)?),
+        (If(condition, success, failure), Snapshot::If(status, cur)) => {
+            let cur = match status {
+                Running => child(0, (*condition).clone(), *cur)?,
+                Success => child(1, (*success).clone(), *cur)?,
+                Failure => child(2, (*failure).clone(), *cur)?,
+            };
+            IfState(condition, success, failure, status, cur)
+        }
+        // A terminated composite keeps the state of its last child.
+        (Select(sel), Snapshot::Composite(i, cur)) if i <= sel.len() && !sel.is_empty() => {
+            let j = i.min(sel.len() - 1);
+            let cur = child(j, sel[j].clone(), *cur)?;
+            SelectState(sel, i, cur)
+        }
+        (Sequence(seq), Snapshot::Composite(i, cur)) if i <= seq.len() && !seq.is_empty() => {
+            let j = i.min(seq.len() - 1);
+            let cur = child(j, seq[j].clone(), *cur)?;
+            SequenceState(seq, i, cur)
+        }
+        (While(ev, rep), Snapshot::While(ev_cursor, i, cur)) if i < rep.len() => {
+            let ev_cursor = child(0, *ev, *ev_cursor)?;
+            let cur = child(i + 1, rep[i].clone(), *cur)?;
+            WhileState(ev_cursor, rep, i, cur)
+        }
+        (WhenAll(all), Snapshot::Parallel(cursors)) if all.len() == cursors.len() => {
+            let cursors = restore_parallel(&all, cursors, path)?;
+            WhenAllState(all, cursors)
+        }
+        (WhenAny(all), Snapshot::Parallel(cursors)) if all.len() == cursors.len() => {
+            let cursors = restore_parallel(&all, cursors, path)?;
+            WhenAnyState(all, cursors)
+        }
+        (After(seq), Snapshot::After(i, cursors))
+            if seq.len() == cursors.len() && i <= seq.len() =>
+        {
+            let cursors = seq
+                .into_iter()
+                .zip(cursors)
+                .enumerate()
+                .map(|(j, (ev, cur))| child(j, ev, cur).map(|cur| *cur))
+                .collect::<Result<_, _>>()?;
+            AfterState(i, cursors)
+        }
+        _ => return Err(SnapshotError { path: path.clone() }),
+    })
+}
+
+fn restore_parallel<A: Clone, S>(
+    all: &[Behavior<A>],
+    cursors: Vec<Option<Snapshot<S>>>,
+    path: &mut Vec<usize>,
+) -> Result<Vec<Option<State<A, S>>>, SnapshotError> {
+    all.iter()
+        .zip(cursors)
+        .enumerate()
+        .map(|(j, (ev, cur))| match cur {
+            Some(cur) => {
+                path.push(j);
+                let res = restore(ev.clone(), cur, path);
+                path.pop();
+                res.map(Some)
+            }
+            None => Ok(None),
+        })
+        .collect()
+}
+
+impl<A: Clone, S> State<A, S> {
+    /// Returns the progress of the state without copies of the behavior.
+    ///
+    /// ```
+    /// use ai_behavior::{Action, Sequence, State, Wait};
+    ///
+    /// let behavior = Sequence(vec![Wait(1.0), Action("jump")]);
+    /// let state: State<_, ()> = State::new(behavior.clone());
+    /// let snapshot = state.snapshot();
+    /// assert_eq!(State::from_snapshot(behavior, snapshot), Ok(state));
+    /// ```
+    pub fn snapshot(&self) -> Snapshot<S>
+    where
+        S: Clone,
+    {
+        let boxed = |cur: &State<A, S>| Box::new(cur.snapshot());
+        match *self {
+            WaitForPressedState(_) | WaitForReleasedState(_) | WaitForeverState => Snapshot::Leaf,
+            ActionState(_, ref s, started) => Snapshot::Action(s.clone(), started),
+            WaitState(_, t) => Snapshot::Wait(t),
+            FailState(ref cur) | AlwaysSucceedState(ref cur) => Snapshot::Decorator(boxed(cur)),
+            IfState(_, _, _, status, ref cur) => Snapshot::If(status, boxed(cur)),
+            SelectState(_, i, ref cur) | SequenceState(_, i, ref cur) => {
+                Snapshot::Composite(i, boxed(cur))
+            }
+            WhileState(ref ev_cursor, _, i, ref cur) => {
+                Snapshot::While(boxed(ev_cursor), i, boxed(cur))
+            }
+            WhenAllState(_, ref cursors) | WhenAnyState(_, ref cursors) => Snapshot::Parallel(
+                cursors
+                    .iter()
+                    .map(|cur| cur.as_ref().map(State::snapshot))
+                    .collect(),
+            ),
+            AfterState(i, ref cursors) => {
+                Snapshot::After(i, cursors.iter().map(State::snapshot).collect())
+            }
+        }
+    }
+
+    /// Creates the state of a behavior from a snapshot taken with `State::snapshot`.
+    ///
+    /// Returns an error with the path of the first node
+    /// where the snapshot does not fit the behavior.
+    pub fn from_snapshot(
+        behavior: Behavior<A>,
+        snapshot: Snapshot<S>,
+    ) -> Result<State<A, S>, SnapshotError> {
+        restore(behavior, snapshot, &mut vec![])
+    }
+}
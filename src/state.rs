@@ -1,4 +1,6 @@
 use std::f64;
+use std::fmt;
+use std::mem;
 
 use crate::state::State::{
     ActionState, AfterState, AlwaysSucceedState, FailState, IfState, SelectState, SequenceState,
@@ -6,8 +8,9 @@ use crate::state::State::{
     WhenAnyState, WhileState,
 };
 use crate::{
-    Action, After, AlwaysSucceed, Behavior, Fail, Failure, If, Running, Select, Sequence, Status,
-    Success, Wait, WaitForPressed, WaitForReleased, WaitForever, WhenAll, WhenAny, While,
+    Action, ActionHandler, After, AlwaysSucceed, Behavior, Fail, Failure, FailureReason, If,
+    Observer, Reason, Running, Select, Sequence, Status, Success, Wait, WaitForPressed,
+    WaitForReleased, WaitForever, WhenAll, WhenAny, While,
 };
 use input::{GenericEvent, UpdateEvent};
 
@@ -15,6 +18,9 @@ use input::{GenericEvent, UpdateEvent};
 pub const RUNNING: (Status, f64) = (Running, 0.0);
 
 /// The arguments in the action callback.
+///
+/// Use `ActionArgs::new` to create the arguments outside of a `State`,
+/// e.g. to call an action handler directly in tests.
 pub struct ActionArgs<'a, E: 'a, A: 'a, S: 'a> {
     /// The event.
     pub event: &'a E,
@@ -24,17 +30,70 @@ pub struct ActionArgs<'a, E: 'a, A: 'a, S: 'a> {
     pub action: &'a A,
     /// The state of the running action, if any.
     pub state: &'a mut Option<S>,
+    // The reason for failing, if any.
+    reason: &'a mut Option<Reason>,
+}
+
+impl<'a, E, A, S> ActionArgs<'a, E, A, S> {
+    /// Creates the arguments for an action.
+    ///
+    /// The reason given with `set_reason` or `fail` is stored in `reason`.
+    pub fn new(
+        event: &'a E,
+        dt: f64,
+        action: &'a A,
+        state: &'a mut Option<S>,
+        reason: &'a mut Option<Reason>,
+    ) -> Self {
+        ActionArgs {
+            event,
+            dt,
+            action,
+            state,
+            reason,
+        }
+    }
+
+    /// Sets the reason for failing, for when the action returns `Failure`.
+    ///
+    /// If the failure makes the behavior fail,
+    /// the reason is returned by `State::event_with_reason`.
+    pub fn set_reason<R: Into<Reason>>(&mut self, reason: R) {
+        *self.reason = Some(reason.into());
+    }
+
+    /// Fails the action with a reason like `set_reason`,
+    /// returning `Failure` with the remaining delta time.
+    pub fn fail<R: Into<Reason>>(mut self, reason: R) -> (Status, f64) {
+        self.set_reason(reason);
+        (Failure, self.dt)
+    }
+}
+
+impl<'a, E, A: fmt::Debug, S: fmt::Debug> fmt::Debug for ActionArgs<'a, E, A, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Generic events are not required to implement `Debug`.
+        f.debug_struct("ActionArgs")
+            .field("dt", &self.dt)
+            .field("action", &self.action)
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
 }
 
 /// Keeps track of a behavior.
-#[derive(Clone, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub enum State<A, S> {
     /// Returns `Success` when button is pressed.
     WaitForPressedState(input::Button),
     /// Returns `Success` when button is released.
     WaitForReleasedState(input::Button),
     /// Executes an action.
-    ActionState(A, Option<S>),
+    ///
+    /// Option<S>: The action state
+    ///
+    /// bool: Whether the action started and did not terminate yet
+    ActionState(A, Option<S>, bool),
     /// Converts `Success` into `Failure` and vice versa.
     FailState(Box<State<A, S>>),
     /// Ignores failures and always return `Success`.
@@ -47,42 +106,71 @@ pub enum State<A, S> {
     WaitState(f64, f64),
     /// Waits forever.
     WaitForeverState,
-    /// Keeps track of an `If` behavior.
+    /// Keeps track of an `If` behavior,
+    /// with the condition, success and failure behaviors.
     /// If status is `Running`, then it evaluates the condition.
     /// If status is `Success`, then it evaluates the success behavior.
     /// If status is `Failure`, then it evaluates the failure behavior.
-    IfState(Box<Behavior<A>>, Box<Behavior<A>>, Status, Box<State<A, S>>),
+    IfState(
+        Box<Behavior<A>>,
+        Box<Behavior<A>>,
+        Box<Behavior<A>>,
+        Status,
+        Box<State<A, S>>,
+    ),
     /// Keeps track of a `Select` behavior.
     SelectState(Vec<Behavior<A>>, usize, Box<State<A, S>>),
     /// Keeps track of an `Sequence` behavior.
     SequenceState(Vec<Behavior<A>>, usize, Box<State<A, S>>),
     /// Keeps track of a `While` behavior.
     WhileState(Box<State<A, S>>, Vec<Behavior<A>>, usize, Box<State<A, S>>),
-    /// Keeps track of a `WhenAll` behavior.
-    WhenAllState(Vec<Option<State<A, S>>>),
-    /// Keeps track of a `WhenAny` behavior.
-    WhenAnyState(Vec<Option<State<A, S>>>),
+    /// Keeps track of a `WhenAll` behavior, with its behaviors.
+    ///
+    /// Terminated behaviors have no state.
+    WhenAllState(Vec<Behavior<A>>, Vec<Option<State<A, S>>>),
+    /// Keeps track of a `WhenAny` behavior, with its behaviors.
+    ///
+    /// Terminated behaviors have no state.
+    WhenAnyState(Vec<Behavior<A>>, Vec<Option<State<A, S>>>),
     /// Keeps track of an `After` behavior.
     AfterState(usize, Vec<State<A, S>>),
 }
 
+// Passed down the tree while updating.
+struct Context<'a, F, O> {
+    f: &'a mut F,
+    observer: &'a mut O,
+    // The path of the node being updated,
+    // only tracked when the observer or the failure needs it.
+    path: Vec<usize>,
+    track_path: bool,
+    // The reason given by the last action.
+    reason: Option<Reason>,
+    // The failure, while parents fail too.
+    failure: Option<Origin>,
+}
+
+// The path and reason of a failure.
+type Origin = (Vec<usize>, Option<Reason>);
+
 // `Sequence` and `Select` share same algorithm.
 //
 // `Sequence` fails if any fails and succeeds when all succeeds.
 // `Select` succeeds if any succeeds and fails when all fails.
-fn sequence<A, S, E, F>(
+fn sequence<A, S, E, F, O>(
     select: bool,
     upd: Option<f64>,
     seq: &[Behavior<A>],
     i: &mut usize,
     cursor: &mut Box<State<A, S>>,
     e: &E,
-    f: &mut F,
+    cx: &mut Context<F, O>,
 ) -> (Status, f64)
 where
     A: Clone,
     E: GenericEvent,
-    F: FnMut(ActionArgs<E, A, S>) -> (Status, f64),
+    F: ActionHandler<E, A, S>,
+    O: Observer<A, S>,
 {
     let (status, inv_status) = if select {
         // `Select`
@@ -94,7 +182,8 @@ where
     let mut remaining_dt = upd.unwrap_or(0.0);
     let mut remaining_e;
     while *i < seq.len() {
-        match cursor.event(
+        match cursor.tick_child(
+            *i,
             match upd {
                 Some(_) => {
                     remaining_e = UpdateEvent::from_dt(remaining_dt, e).unwrap();
@@ -102,7 +191,7 @@ where
                 }
                 _ => e,
             },
-            f,
+            cx,
         ) {
             (Running, _) => {
                 break;
@@ -148,17 +237,18 @@ where
 //
 // `WhenAll` fails if any fails and succeeds when all succeeds.
 // `WhenAny` succeeds if any succeeds and fails when all fails.
-fn when_all<A, S, E, F>(
+fn when_all<A, S, E, F, O>(
     any: bool,
     upd: Option<f64>,
-    cursors: &mut Vec<Option<State<A, S>>>,
+    cursors: &mut [Option<State<A, S>>],
     e: &E,
-    f: &mut F,
+    cx: &mut Context<F, O>,
 ) -> (Status, f64)
 where
     A: Clone,
     E: GenericEvent,
-    F: FnMut(ActionArgs<E, A, S>) -> (Status, f64),
+    F: ActionHandler<E, A, S>,
+    O: Observer<A, S>,
 {
     let (status, inv_status) = if any {
         // `WhenAny`
@@ -171,43 +261,41 @@ where
     let mut min_dt = f64::MAX;
     // Count number of terminated events.
     let mut terminated = 0;
-    for cur in cursors.iter_mut() {
-        match *cur {
-            None => {}
-            Some(ref mut cur) => {
-                match cur.event(e, f) {
-                    (Running, _) => {
-                        continue;
-                    }
-                    (s, new_dt) if s == inv_status => {
-                        // Fail for `WhenAll`.
-                        // Succeed for `WhenAny`.
-                        return (inv_status, new_dt);
-                    }
-                    (s, new_dt) if s == status => {
-                        min_dt = min_dt.min(new_dt);
+    for j in 0..cursors.len() {
+        if let Some(ref mut cur) = cursors[j] {
+            match cur.tick_child(j, e, cx) {
+                (Running, _) => {
+                    continue;
+                }
+                (s, new_dt) if s == inv_status => {
+                    // The other events are stopped.
+                    for (k, cur) in cursors.iter_mut().enumerate() {
+                        if let (true, Some(cur)) = (k != j, cur) {
+                            cur.abort_child(k, cx);
+                        }
                     }
-                    _ => unreachable!(),
+                    // Fail for `WhenAll`.
+                    // Succeed for `WhenAny`.
+                    return (inv_status, new_dt);
+                }
+                (s, new_dt) if s == status => {
+                    min_dt = min_dt.min(new_dt);
                 }
+                _ => unreachable!(),
             }
         }
 
         terminated += 1;
-        *cur = None;
+        cursors[j] = None;
     }
-    match terminated {
-        // If there are no events, there is a whole 'dt' left.
-        0 if cursors.is_empty() => (
-            status,
-            match upd {
-                Some(dt) => dt,
-                // Other kind of events happen instantly.
-                _ => 0.0,
-            },
-        ),
+    if terminated == cursors.len() {
         // If all events terminated, the least delta time is left.
-        n if cursors.len() == n => (status, min_dt),
-        _ => RUNNING,
+        // If there are no events, or they terminated before this event,
+        // there is a whole 'dt' left.
+        // Other kind of events happen instantly.
+        (status, min_dt.min(upd.unwrap_or(0.0)))
+    } else {
+        RUNNING
     }
 }
 
@@ -217,14 +305,14 @@ impl<A: Clone, S> State<A, S> {
         match behavior {
             WaitForPressed(button) => WaitForPressedState(button),
             WaitForReleased(button) => WaitForReleasedState(button),
-            Action(action) => ActionState(action, None),
+            Action(action) => ActionState(action, None, false),
             Fail(ev) => FailState(Box::new(State::new(*ev))),
             AlwaysSucceed(ev) => AlwaysSucceedState(Box::new(State::new(*ev))),
             Wait(dt) => WaitState(dt, 0.0),
             WaitForever => WaitForeverState,
             If(condition, success, failure) => {
-                let state = State::new(*condition);
-                IfState(success, failure, Running, Box::new(state))
+                let state = State::new((*condition).clone());
+                IfState(condition, success, failure, Running, Box::new(state))
             }
             Select(sel) => {
                 let state = State::new(sel[0].clone());
@@ -238,12 +326,162 @@ impl<A: Clone, S> State<A, S> {
                 let state = State::new(rep[0].clone());
                 WhileState(Box::new(State::new(*ev)), rep, 0, Box::new(state))
             }
-            WhenAll(all) => WhenAllState(all.into_iter().map(|ev| Some(State::new(ev))).collect()),
-            WhenAny(all) => WhenAnyState(all.into_iter().map(|ev| Some(State::new(ev))).collect()),
+            WhenAll(all) => {
+                let cursors = all.iter().map(|ev| Some(State::new(ev.clone()))).collect();
+                WhenAllState(all, cursors)
+            }
+            WhenAny(all) => {
+                let cursors = all.iter().map(|ev| Some(State::new(ev.clone()))).collect();
+                WhenAnyState(all, cursors)
+            }
             After(seq) => AfterState(0, seq.into_iter().map(State::new).collect()),
         }
     }
 
+    /// Returns the behavior that the state was created from.
+    pub fn behavior(&self) -> Behavior<A> {
+        match *self {
+            WaitForPressedState(button) => WaitForPressed(button),
+            WaitForReleasedState(button) => WaitForReleased(button),
+            ActionState(ref action, ..) => Action(action.clone()),
+            FailState(ref cur) => Fail(Box::new(cur.behavior())),
+            AlwaysSucceedState(ref cur) => AlwaysSucceed(Box::new(cur.behavior())),
+            WaitState(dt, _) => Wait(dt),
+            WaitForeverState => WaitForever,
+            IfState(ref condition, ref success, ref failure, _, _) => {
+                If(condition.clone(), success.clone(), failure.clone())
+            }
+            SelectState(ref sel, _, _) => Select(sel.clone()),
+            SequenceState(ref seq, _, _) => Sequence(seq.clone()),
+            WhileState(ref ev_cursor, ref rep, _, _) => {
+                While(Box::new(ev_cursor.behavior()), rep.clone())
+            }
+            WhenAllState(ref all, _) => WhenAll(all.clone()),
+            WhenAnyState(ref all, _) => WhenAny(all.clone()),
+            AfterState(_, ref cursors) => After(cursors.iter().map(State::behavior).collect()),
+        }
+    }
+
+    /// Returns the paths of the leaf behaviors that are currently running.
+    ///
+    /// A path is the list of child indices from the root behavior.
+    /// Children are numbered in the order they appear in the behavior,
+    /// e.g. `If(condition, success, failure)` has children `0`, `1` and `2`,
+    /// and the body of `While(condition, body)` starts at `1`.
+    ///
+    /// Parallel behaviors, such as `WhenAll` and `After`,
+    /// return one path for every running branch.
+    pub fn active_paths(&self) -> Vec<Vec<usize>> {
+        let mut paths = vec![];
+        self.collect_active_paths(&mut vec![], &mut paths);
+        paths
+    }
+
+    fn collect_active_paths(&self, path: &mut Vec<usize>, paths: &mut Vec<Vec<usize>>) {
+        let mut leaf = true;
+        self.for_each_active_child(&mut |index, state| {
+            leaf = false;
+            path.push(index);
+            state.collect_active_paths(path, paths);
+            path.pop();
+        });
+        if leaf {
+            paths.push(path.clone());
+        }
+    }
+
+    // Calls closure with index and state of every running child.
+    fn for_each_active_child(&self, f: &mut dyn FnMut(usize, &State<A, S>)) {
+        match *self {
+            WaitForPressedState(_)
+            | WaitForReleasedState(_)
+            | ActionState(..)
+            | WaitState(..)
+            | WaitForeverState => {}
+            FailState(ref cur) | AlwaysSucceedState(ref cur) => f(0, cur),
+            IfState(_, _, _, status, ref cur) => f(if_index(status), cur),
+            SelectState(_, i, ref cur) | SequenceState(_, i, ref cur) => f(i, cur),
+            WhileState(ref ev_cursor, _, i, ref cur) => {
+                f(0, ev_cursor);
+                f(i + 1, cur);
+            }
+            WhenAllState(_, ref cursors) | WhenAnyState(_, ref cursors) => {
+                for (j, cur) in cursors.iter().enumerate() {
+                    if let Some(ref cur) = *cur {
+                        f(j, cur);
+                    }
+                }
+            }
+            AfterState(i, ref cursors) => {
+                for (j, cur) in cursors.iter().enumerate().skip(i) {
+                    f(j, cur);
+                }
+            }
+        }
+    }
+
+    // Calls closure with index and state of every running child, mutably.
+    fn for_each_active_child_mut(&mut self, f: &mut dyn FnMut(usize, &mut State<A, S>)) {
+        match *self {
+            WaitForPressedState(_)
+            | WaitForReleasedState(_)
+            | ActionState(..)
+            | WaitState(..)
+            | WaitForeverState => {}
+            FailState(ref mut cur) | AlwaysSucceedState(ref mut cur) => f(0, cur),
+            IfState(_, _, _, status, ref mut cur) => f(if_index(status), cur),
+            SelectState(_, i, ref mut cur) | SequenceState(_, i, ref mut cur) => f(i, cur),
+            WhileState(ref mut ev_cursor, _, i, ref mut cur) => {
+                f(0, ev_cursor);
+                f(i + 1, cur);
+            }
+            WhenAllState(_, ref mut cursors) | WhenAnyState(_, ref mut cursors) => {
+                for (j, cur) in cursors.iter_mut().enumerate() {
+                    if let Some(ref mut cur) = *cur {
+                        f(j, cur);
+                    }
+                }
+            }
+            AfterState(i, ref mut cursors) => {
+                for (j, cur) in cursors.iter_mut().enumerate().skip(i) {
+                    f(j, cur);
+                }
+            }
+        }
+    }
+
+    // Notifies the observer that this node and every running node below it
+    // are stopped, starting with the leaves.
+    // Stopped actions that started are halted, dropping their state.
+    fn abort<E, F, O>(&mut self, cx: &mut Context<F, O>)
+    where
+        F: ActionHandler<E, A, S>,
+        O: Observer<A, S>,
+    {
+        self.for_each_active_child_mut(&mut |index, cur| cur.abort_child(index, cx));
+        if let ActionState(ref action, ref mut state, ref mut started) = *self {
+            let state = state.take();
+            if mem::replace(started, false) {
+                cx.f.halt(action, state);
+            }
+        }
+        cx.observer.abort(&cx.path);
+    }
+
+    fn abort_child<E, F, O>(&mut self, index: usize, cx: &mut Context<F, O>)
+    where
+        F: ActionHandler<E, A, S>,
+        O: Observer<A, S>,
+    {
+        if cx.track_path {
+            cx.path.push(index);
+        }
+        self.abort(cx);
+        if cx.track_path {
+            cx.path.pop();
+        }
+    }
+
     /// Updates the cursor that tracks an event.
     ///
     /// The action need to return status and remaining delta time.
@@ -251,12 +489,280 @@ impl<A: Clone, S> State<A, S> {
     ///
     /// Passes event, delta time in seconds, action and state to closure.
     /// The closure should return a status and remaining delta time.
+    ///
+    /// When a running action is stopped because its parent terminated,
+    /// its state is dropped, so the action starts over with `None`
+    /// if it runs again. Use `handle` to be notified when actions stop.
     pub fn event<E, F>(&mut self, e: &E, f: &mut F) -> (Status, f64)
     where
         E: GenericEvent,
         F: FnMut(ActionArgs<E, A, S>) -> (Status, f64),
     {
+        self.event_with_observer(e, f, &mut ())
+    }
+
+    /// Updates the cursor like `event`,
+    /// notifying the observer when nodes are entered, exited and aborted.
+    pub fn event_with_observer<E, F, O>(
+        &mut self,
+        e: &E,
+        f: &mut F,
+        observer: &mut O,
+    ) -> (Status, f64)
+    where
+        E: GenericEvent,
+        F: FnMut(ActionArgs<E, A, S>) -> (Status, f64),
+        O: Observer<A, S>,
+    {
+        self.run(e, f, observer, false).0
+    }
+
+    /// Updates the cursor like `event`, running actions with a handler.
+    pub fn handle<E, H>(&mut self, e: &E, handler: &mut H) -> (Status, f64)
+    where
+        E: GenericEvent,
+        H: ActionHandler<E, A, S>,
+    {
+        self.handle_with_observer(e, handler, &mut ())
+    }
+
+    /// Updates the cursor like `handle`,
+    /// notifying the observer when nodes are entered, exited and aborted.
+    pub fn handle_with_observer<E, H, O>(
+        &mut self,
+        e: &E,
+        handler: &mut H,
+        observer: &mut O,
+    ) -> (Status, f64)
+    where
+        E: GenericEvent,
+        H: ActionHandler<E, A, S>,
+        O: Observer<A, S>,
+    {
+        self.run(e, handler, observer, false).0
+    }
+
+    /// Updates the cursor like `event`,
+    /// returning why the behavior failed instead of `Failure`.
+    ///
+    /// The failure starts at the node that failed first,
+    /// e.g. an action in a `Sequence` or the last child of a `Select`.
+    ///
+    /// ```
+    /// use ai_behavior::{Action, Sequence, State, Success, Wait};
+    /// use input::{Event, UpdateArgs};
+    ///
+    /// let mut state: State<_, ()> = State::new(Sequence(vec![Wait(1.0), Action("open")]));
+    /// let e: Event = UpdateArgs { dt: 2.0 }.into();
+    /// let failure = state
+    ///     .event_with_reason(&e, &mut |args| match *args.action {
+    ///         "open" => args.fail("The door is locked"),
+    ///         _ => (Success, args.dt),
+    ///     })
+    ///     .unwrap_err();
+    /// assert_eq!(failure.to_string(), "[1] failed: The door is locked");
+    /// ```
+    pub fn event_with_reason<E, F>(
+        &mut self,
+        e: &E,
+        f: &mut F,
+    ) -> Result<(Status, f64), FailureReason>
+    where
+        E: GenericEvent,
+        F: FnMut(ActionArgs<E, A, S>) -> (Status, f64),
+    {
+        self.handle_with_reason(e, f, &mut ())
+    }
+
+    /// Updates the cursor like `handle_with_observer`,
+    /// returning why the behavior failed like `event_with_reason`.
+    pub fn handle_with_reason<E, H, O>(
+        &mut self,
+        e: &E,
+        handler: &mut H,
+        observer: &mut O,
+    ) -> Result<(Status, f64), FailureReason>
+    where
+        E: GenericEvent,
+        H: ActionHandler<E, A, S>,
+        O: Observer<A, S>,
+    {
+        match self.run(e, handler, observer, true) {
+            ((Failure, dt), Some((path, reason))) => Err(FailureReason { path, dt, reason }),
+            (res, _) => Ok(res),
+        }
+    }
+
+    fn run<E, F, O>(
+        &mut self,
+        e: &E,
+        f: &mut F,
+        observer: &mut O,
+        reasons: bool,
+    ) -> ((Status, f64), Option<Origin>)
+    where
+        E: GenericEvent,
+        F: ActionHandler<E, A, S>,
+        O: Observer<A, S>,
+    {
+        let mut cx = Context {
+            f,
+            observer,
+            path: vec![],
+            track_path: O::NEEDS_PATH || reasons,
+            reason: None,
+            failure: None,
+        };
+        let res = self.tick(e, &mut cx);
+        (res, cx.failure)
+    }
+
+    /// Starts the behavior over,
+    /// halting the running actions with the handler.
+    ///
+    /// A closure passed as handler only drops the state of the actions.
+    pub fn reset<E, H>(&mut self, handler: &mut H)
+    where
+        H: ActionHandler<E, A, S>,
+    {
+        self.reset_with_observer(handler, &mut ())
+    }
+
+    /// Starts the behavior over like `reset`,
+    /// notifying the observer that the running nodes are aborted.
+    pub fn reset_with_observer<E, H, O>(&mut self, handler: &mut H, observer: &mut O)
+    where
+        H: ActionHandler<E, A, S>,
+        O: Observer<A, S>,
+    {
+        self.restart_with_observer(&[], handler, observer);
+    }
+
+    /// Starts the running node at the path over,
+    /// halting the running actions below it with the handler,
+    /// e.g. `[1]` restarts the second child of a running `Sequence`.
+    ///
+    /// Returns `false` if there is no running node at the path.
+    pub fn restart<E, H>(&mut self, path: &[usize], handler: &mut H) -> bool
+    where
+        H: ActionHandler<E, A, S>,
+    {
+        self.restart_with_observer(path, handler, &mut ())
+    }
+
+    /// Starts the running node at the path over like `restart`,
+    /// notifying the observer that the node and its running children are aborted.
+    pub fn restart_with_observer<E, H, O>(
+        &mut self,
+        path: &[usize],
+        handler: &mut H,
+        observer: &mut O,
+    ) -> bool
+    where
+        H: ActionHandler<E, A, S>,
+        O: Observer<A, S>,
+    {
+        let mut state = self;
+        for &index in path {
+            state = match state.active_child_mut(index) {
+                Some(child) => child,
+                None => return false,
+            };
+        }
+        let mut cx = Context {
+            f: handler,
+            observer,
+            path: if O::NEEDS_PATH { path.to_vec() } else { vec![] },
+            track_path: O::NEEDS_PATH,
+            reason: None,
+            failure: None,
+        };
+        state.abort(&mut cx);
+        *state = State::new(state.behavior());
+        true
+    }
+
+    // Stops the running nodes, halting the running actions with the handler.
+    pub(crate) fn halt<E, H>(&mut self, handler: &mut H)
+    where
+        H: ActionHandler<E, A, S>,
+    {
+        let mut cx = Context {
+            f: handler,
+            observer: &mut (),
+            path: vec![],
+            track_path: false,
+            reason: None,
+            failure: None,
+        };
+        self.abort(&mut cx);
+    }
+
+    // Returns the running child with the index.
+    fn active_child_mut(&mut self, index: usize) -> Option<&mut State<A, S>> {
+        match *self {
+            FailState(ref mut cur) | AlwaysSucceedState(ref mut cur) if index == 0 => Some(cur),
+            IfState(_, _, _, status, ref mut cur) if index == if_index(status) => Some(cur),
+            SelectState(_, i, ref mut cur) | SequenceState(_, i, ref mut cur) if index == i => {
+                Some(cur)
+            }
+            WhileState(ref mut ev_cursor, _, _, _) if index == 0 => Some(ev_cursor),
+            WhileState(_, _, i, ref mut cur) if index == i + 1 => Some(cur),
+            WhenAllState(_, ref mut cursors) | WhenAnyState(_, ref mut cursors) => {
+                cursors.get_mut(index).and_then(Option::as_mut)
+            }
+            AfterState(i, ref mut cursors) if index >= i => cursors.get_mut(index),
+            _ => None,
+        }
+    }
+
+    fn tick<E, F, O>(&mut self, e: &E, cx: &mut Context<F, O>) -> (Status, f64)
+    where
+        E: GenericEvent,
+        F: ActionHandler<E, A, S>,
+        O: Observer<A, S>,
+    {
+        cx.observer.enter(&cx.path, self);
         let upd = e.update(|args| Some(args.dt)).unwrap_or(None);
+        if let Some(dt) = upd {
+            cx.observer.update(&cx.path, dt);
+        }
+        // The failure of the last child that terminated is kept
+        // when parents fail too.
+        cx.failure = None;
+        let (status, dt) = self.update(e, upd, cx);
+        let reason = cx.reason.take();
+        if status != Failure {
+            cx.failure = None;
+        } else if cx.track_path && cx.failure.is_none() {
+            cx.failure = Some((cx.path.clone(), reason));
+        }
+        cx.observer.exit(&cx.path, status, dt);
+        (status, dt)
+    }
+
+    fn tick_child<E, F, O>(&mut self, index: usize, e: &E, cx: &mut Context<F, O>) -> (Status, f64)
+    where
+        E: GenericEvent,
+        F: ActionHandler<E, A, S>,
+        O: Observer<A, S>,
+    {
+        if cx.track_path {
+            cx.path.push(index);
+        }
+        let res = self.tick(e, cx);
+        if cx.track_path {
+            cx.path.pop();
+        }
+        res
+    }
+
+    fn update<E, F, O>(&mut self, e: &E, upd: Option<f64>, cx: &mut Context<F, O>) -> (Status, f64)
+    where
+        E: GenericEvent,
+        F: ActionHandler<E, A, S>,
+        O: Observer<A, S>,
+    {
         match (upd, self) {
             (None, &mut WaitForPressedState(button)) => {
                 e.press(|button_pressed| {
@@ -284,21 +790,32 @@ impl<A: Clone, S> State<A, S> {
                 })
                 .unwrap_or(RUNNING)
             }
-            (_, &mut ActionState(ref action, ref mut state)) => {
+            (_, &mut ActionState(ref action, ref mut state, ref mut started)) => {
+                if !mem::replace(started, true) {
+                    if let Some(new_state) = cx.f.start(action) {
+                        *state = Some(new_state);
+                    }
+                }
                 // Execute action.
-                f(ActionArgs {
+                let res = cx.f.tick(ActionArgs {
                     event: e,
                     dt: upd.unwrap_or(0.0),
                     action,
                     state,
-                })
+                    reason: &mut cx.reason,
+                });
+                // The run ends when the action terminates.
+                if res.0 != Running {
+                    *started = false;
+                }
+                res
             }
-            (_, &mut FailState(ref mut cur)) => match cur.event(e, f) {
+            (_, &mut FailState(ref mut cur)) => match cur.tick_child(0, e, cx) {
                 (Running, dt) => (Running, dt),
                 (Failure, dt) => (Success, dt),
                 (Success, dt) => (Failure, dt),
             },
-            (_, &mut AlwaysSucceedState(ref mut cur)) => match cur.event(e, f) {
+            (_, &mut AlwaysSucceedState(ref mut cur)) => match cur.tick_child(0, e, cx) {
                 (Running, dt) => (Running, dt),
                 (_, dt) => (Success, dt),
             },
@@ -312,14 +829,14 @@ impl<A: Clone, S> State<A, S> {
                     RUNNING
                 }
             }
-            (_, &mut IfState(ref success, ref failure, ref mut status, ref mut state)) => {
+            (_, &mut IfState(_, ref success, ref failure, ref mut status, ref mut state)) => {
                 let mut remaining_dt = upd.unwrap_or(0.0);
                 let remaining_e;
                 // Run in a loop to evaluate success or failure with
                 // remaining delta time after condition.
                 loop {
                     *status = match *status {
-                        Running => match state.event(e, f) {
+                        Running => match state.tick_child(0, e, cx) {
                             (Running, dt) => {
                                 return (Running, dt);
                             }
@@ -335,7 +852,8 @@ impl<A: Clone, S> State<A, S> {
                             }
                         },
                         _ => {
-                            return state.event(
+                            return state.tick_child(
+                                if_index(*status),
                                 match upd {
                                     Some(_) => {
                                         remaining_e =
@@ -344,7 +862,7 @@ impl<A: Clone, S> State<A, S> {
                                     }
                                     _ => e,
                                 },
-                                f,
+                                cx,
                             );
                         }
                     }
@@ -352,23 +870,30 @@ impl<A: Clone, S> State<A, S> {
             }
             (_, &mut SelectState(ref seq, ref mut i, ref mut cursor)) => {
                 let select = true;
-                sequence(select, upd, seq, i, cursor, e, f)
+                sequence(select, upd, seq, i, cursor, e, cx)
             }
             (_, &mut SequenceState(ref seq, ref mut i, ref mut cursor)) => {
                 let select = false;
-                sequence(select, upd, seq, i, cursor, e, f)
+                sequence(select, upd, seq, i, cursor, e, cx)
             }
             (_, &mut WhileState(ref mut ev_cursor, ref rep, ref mut i, ref mut cursor)) => {
                 // If the event terminates, do not execute the loop.
-                match ev_cursor.event(e, f) {
+                match ev_cursor.tick_child(0, e, cx) {
                     (Running, _) => {}
-                    x => return x,
+                    x => {
+                        cursor.abort_child(*i + 1, cx);
+                        return x;
+                    }
                 };
                 let cur = cursor;
                 let mut remaining_dt = upd.unwrap_or(0.0);
                 let mut remaining_e;
+                // The body index and delta time when the current pass started.
+                let start = *i;
+                let mut pass_dt = remaining_dt;
                 loop {
-                    match cur.event(
+                    match cur.tick_child(
+                        *i + 1,
                         match upd {
                             Some(_) => {
                                 remaining_e = UpdateEvent::from_dt(remaining_dt, e).unwrap();
@@ -376,18 +901,15 @@ impl<A: Clone, S> State<A, S> {
                             }
                             _ => e,
                         },
-                        f,
+                        cx,
                     ) {
-                        (Failure, x) => return (Failure, x),
-                        (Running, _) => break,
-                        (Success, new_dt) => {
-                            remaining_dt = match upd {
-                                // Change update event with remaining delta time.
-                                Some(_) => new_dt,
-                                // Other events are 'consumed' and not passed to next.
-                                _ => return RUNNING,
-                            }
+                        (Failure, x) => {
+                            ev_cursor.abort_child(0, cx);
+                            return (Failure, x);
                         }
+                        (Running, _) => break,
+                        // Change update event with remaining delta time.
+                        (Success, new_dt) => remaining_dt = new_dt,
                     };
                     *i += 1;
                     // If end of repeated events,
@@ -398,43 +920,63 @@ impl<A: Clone, S> State<A, S> {
                     // Create a new cursor for next event.
                     // Use the same pointer to avoid allocation.
                     **cur = State::new(rep[*i].clone());
+                    // Other events are 'consumed' and not passed to next.
+                    if upd.is_none() {
+                        return RUNNING;
+                    }
+                    // A pass over the whole body that takes no time
+                    // would loop forever, so it continues on the next update.
+                    if *i == start {
+                        if remaining_dt >= pass_dt {
+                            return RUNNING;
+                        }
+                        pass_dt = remaining_dt;
+                    }
                 }
                 RUNNING
             }
-            (_, &mut WhenAllState(ref mut cursors)) => {
+            (_, &mut WhenAllState(_, ref mut cursors)) => {
                 let any = false;
-                when_all(any, upd, cursors, e, f)
+                when_all(any, upd, cursors, e, cx)
             }
-            (_, &mut WhenAnyState(ref mut cursors)) => {
+            (_, &mut WhenAnyState(_, ref mut cursors)) => {
                 let any = true;
-                when_all(any, upd, cursors, e, f)
+                when_all(any, upd, cursors, e, cx)
             }
             (_, &mut AfterState(ref mut i, ref mut cursors)) => {
                 // Get the least delta time left over.
                 let mut min_dt = f64::MAX;
                 for j in *i..cursors.len() {
-                    match cursors[j].event(e, f) {
+                    let (status, dt) = match cursors[j].tick_child(j, e, cx) {
                         (Running, _) => {
                             min_dt = 0.0;
+                            continue;
                         }
                         (Success, new_dt) => {
                             // Remaining delta time must be less to succeed.
                             if *i == j && new_dt < min_dt {
                                 *i += 1;
                                 min_dt = new_dt;
-                            } else {
-                                // Return least delta time because
-                                // that is when failure is detected.
-                                return (Failure, min_dt.min(new_dt));
+                                continue;
                             }
+                            // Return least delta time because
+                            // that is when failure is detected.
+                            (Failure, min_dt.min(new_dt))
                         }
-                        (Failure, new_dt) => {
-                            return (Failure, new_dt);
-                        }
+                        (Failure, new_dt) => (Failure, new_dt),
                     };
+                    // The other events are stopped.
+                    for (k, cur) in cursors.iter_mut().enumerate().skip(*i) {
+                        if k != j {
+                            cur.abort_child(k, cx);
+                        }
+                    }
+                    return (status, dt);
                 }
                 if *i == cursors.len() {
-                    (Success, min_dt)
+                    // If there are no events, or they succeeded before this event,
+                    // there is a whole 'dt' left.
+                    (Success, min_dt.min(upd.unwrap_or(0.0)))
                 } else {
                     RUNNING
                 }
@@ -443,3 +985,12 @@ impl<A: Clone, S> State<A, S> {
         }
     }
 }
+
+// Returns the index of the child that an `If` behavior runs.
+fn if_index(status: Status) -> usize {
+    match status {
+        Running => 0,
+        Success => 1,
+        Failure => 2,
+    }
+}
@@ -0,0 +1,379 @@
+use std::error::Error;
+use std::fmt;
+
+use input::{Button, ControllerButton, ControllerHat, HatState, Key, MouseButton};
+
+use crate::{
+    Action, After, AlwaysSucceed, Behavior, Fail, If, Select, Sequence, Wait, WaitForPressed,
+    WaitForReleased, WaitForever, WhenAll, WhenAny, While,
+};
+
+/// An error when parsing the text format, with position in the text.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// Column number in characters, starting at 1.
+    pub column: usize,
+    /// Describes the error.
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Token {
+    Open,
+    Close,
+    Atom(String),
+    Str(String),
+    End,
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    column: usize,
+    // The position of the last token.
+    pos: (usize, usize),
+    // The number of open behaviors.
+    depth: usize,
+}
+
+// The deepest nesting accepted, so parsing does not overflow the stack.
+const MAX_DEPTH: usize = 256;
+
+impl<'a> Parser<'a> {
+    fn error<T, M: Into<String>>(&self, message: M) -> Result<T, ParseError> {
+        Err(ParseError {
+            line: self.pos.0,
+            column: self.pos.1,
+            message: message.into(),
+        })
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn token(&mut self) -> Result<Token, ParseError> {
+        // Skip whitespace and comments.
+        loop {
+            match self.chars.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.bump();
+                }
+                Some(';') => while !matches!(self.bump(), None | Some('\n')) {},
+                _ => break,
+            }
+        }
+        self.pos = (self.line, self.column);
+        match self.chars.peek().cloned() {
+            None => Ok(Token::End),
+            Some('(') => {
+                self.bump();
+                Ok(Token::Open)
+            }
+            Some(')') => {
+                self.bump();
+                Ok(Token::Close)
+            }
+            Some('"') => {
+                self.bump();
+                let mut s = String::new();
+                loop {
+                    match self.bump() {
+                        None => return self.error("Unterminated string"),
+                        Some('"') => return Ok(Token::Str(s)),
+                        Some('\\') => match self.bump() {
+                            Some(c @ '"') | Some(c @ '\\') => s.push(c),
+                            Some('n') => s.push('\n'),
+                            _ => return self.error("Invalid escape in string"),
+                        },
+                        Some(c) => s.push(c),
+                    }
+                }
+            }
+            Some(_) => {
+                let mut s = String::new();
+                while let Some(&c) = self.chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' || c == '"' || c == ';' {
+                        break;
+                    }
+                    s.push(c);
+                    self.bump();
+                }
+                Ok(Token::Atom(s))
+            }
+        }
+    }
+
+    fn atom(&mut self, what: &str) -> Result<String, ParseError> {
+        match self.token()? {
+            Token::Atom(s) | Token::Str(s) => Ok(s),
+            _ => self.error(format!("Expected {}", what)),
+        }
+    }
+
+    fn number<T: std::str::FromStr>(&mut self, what: &str) -> Result<T, ParseError> {
+        let s = self.atom(what)?;
+        match s.parse() {
+            Ok(x) => Ok(x),
+            Err(_) => self.error(format!("Expected {}, found `{}`", what, s)),
+        }
+    }
+
+    fn close(&mut self) -> Result<(), ParseError> {
+        match self.token()? {
+            Token::Close => Ok(()),
+            _ => self.error("Expected `)`"),
+        }
+    }
+
+    fn button(&mut self) -> Result<Button, ParseError> {
+        if self.token()? != Token::Open {
+            return self.error("Expected button, e.g. `(keyboard 97)`");
+        }
+        let button = match &*self.atom("button kind")? {
+            "keyboard" => Button::Keyboard(Key::from(self.number::<u32>("key code")?)),
+            "mouse" => Button::Mouse(MouseButton::from(self.number::<u32>("mouse button")?)),
+            "controller" => Button::Controller(ControllerButton {
+                id: self.number("controller id")?,
+                button: self.number("controller button")?,
+            }),
+            "hat" => {
+                let id = self.number("controller id")?;
+                let which = self.number("hat")?;
+                let state = match &*self.atom("hat state")? {
+                    "Centered" => HatState::Centered,
+                    "Up" => HatState::Up,
+                    "Right" => HatState::Right,
+                    "Down" => HatState::Down,
+                    "Left" => HatState::Left,
+                    "RightUp" => HatState::RightUp,
+                    "RightDown" => HatState::RightDown,
+                    "LeftUp" => HatState::LeftUp,
+                    "LeftDown" => HatState::LeftDown,
+                    s => return self.error(format!("Unknown hat state `{}`", s)),
+                };
+                Button::Hat(ControllerHat { id, state, which })
+            }
+            s => return self.error(format!("Unknown button kind `{}`", s)),
+        };
+        self.close()?;
+        Ok(button)
+    }
+
+    // Parses behaviors until `)`.
+    fn list<A, F>(&mut self, action: &mut F) -> Result<Vec<Behavior<A>>, ParseError>
+    where
+        F: FnMut(&str) -> Result<A, String>,
+    {
+        let mut list = vec![];
+        loop {
+            match self.token()? {
+                Token::Close => return Ok(list),
+                Token::Open => list.push(self.node(action)?),
+                _ => return self.error("Expected `(` or `)`"),
+            }
+        }
+    }
+
+    fn child<A, F>(&mut self, action: &mut F) -> Result<Box<Behavior<A>>, ParseError>
+    where
+        F: FnMut(&str) -> Result<A, String>,
+    {
+        match self.token()? {
+            Token::Open => Ok(Box::new(self.node(action)?)),
+            _ => self.error("Expected `(`"),
+        }
+    }
+
+    // Parses a behavior after `(`.
+    fn node<A, F>(&mut self, action: &mut F) -> Result<Behavior<A>, ParseError>
+    where
+        F: FnMut(&str) -> Result<A, String>,
+    {
+        if self.depth == MAX_DEPTH {
+            return self.error("Behaviors are nested too deeply");
+        }
+        self.depth += 1;
+        let behavior = self.node_body(action);
+        self.depth -= 1;
+        behavior
+    }
+
+    fn node_body<A, F>(&mut self, action: &mut F) -> Result<Behavior<A>, ParseError>
+    where
+        F: FnMut(&str) -> Result<A, String>,
+    {
+        let name = match self.token()? {
+            Token::Atom(name) => name,
+            _ => return self.error("Expected behavior name"),
+        };
+        let behavior = match &*name {
+            "wait" => Wait(self.number("seconds")?),
+            "wait-forever" => WaitForever,
+            "wait-for-pressed" => WaitForPressed(self.button()?),
+            "wait-for-released" => WaitForReleased(self.button()?),
+            "action" => {
+                let s = self.atom("action")?;
+                match action(&s) {
+                    Ok(a) => Action(a),
+                    Err(message) => return self.error(message),
+                }
+            }
+            "fail" => Fail(self.child(action)?),
+            "always-succeed" => AlwaysSucceed(self.child(action)?),
+            "if" => If(
+                self.child(action)?,
+                self.child(action)?,
+                self.child(action)?,
+            ),
+            "while" => {
+                let condition = self.child(action)?;
+                return Ok(While(condition, self.list(action)?));
+            }
+            "select" => return Ok(Select(self.list(action)?)),
+            "sequence" => return Ok(Sequence(self.list(action)?)),
+            "when-all" => return Ok(WhenAll(self.list(action)?)),
+            "when-any" => return Ok(WhenAny(self.list(action)?)),
+            "after" => return Ok(After(self.list(action)?)),
+            _ => return self.error(format!("Unknown behavior `{}`", name)),
+        };
+        self.close()?;
+        Ok(behavior)
+    }
+}
+
+fn button(button: Button) -> String {
+    match button {
+        Button::Keyboard(key) => format!("(keyboard {})", u32::from(key)),
+        Button::Mouse(b) => format!("(mouse {})", u32::from(b)),
+        Button::Controller(b) => format!("(controller {} {})", b.id, b.button),
+        Button::Hat(h) => format!("(hat {} {} {:?})", h.id, h.which, h.state),
+    }
+}
+
+// Quotes the action if it would not be read back as a single atom.
+fn quote(s: &str) -> String {
+    let plain = !s.is_empty()
+        && !s
+            .chars()
+            .any(|c| c.is_whitespace() || c == '(' || c == ')' || c == '"' || c == ';');
+    if plain {
+        s.into()
+    } else {
+        format!(
+            "\"{}\"",
+            s.replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n")
+        )
+    }
+}
+
+fn write<A, F>(out: &mut String, behavior: &Behavior<A>, indent: usize, action: &mut F)
+where
+    F: FnMut(&A) -> String,
+{
+    let name = match *behavior {
+        Wait(dt) => return out.push_str(&format!("(wait {})", dt)),
+        WaitForever => return out.push_str("(wait-forever)"),
+        WaitForPressed(b) => return out.push_str(&format!("(wait-for-pressed {})", button(b))),
+        WaitForReleased(b) => return out.push_str(&format!("(wait-for-released {})", button(b))),
+        Action(ref a) => return out.push_str(&format!("(action {})", quote(&action(a)))),
+        Fail(_) => "fail",
+        AlwaysSucceed(_) => "always-succeed",
+        If(..) => "if",
+        While(..) => "while",
+        Select(_) => "select",
+        Sequence(_) => "sequence",
+        WhenAll(_) => "when-all",
+        WhenAny(_) => "when-any",
+        After(_) => "after",
+    };
+    out.push('(');
+    out.push_str(name);
+    for (_, child) in behavior.children() {
+        out.push('\n');
+        for _ in 0..indent + 1 {
+            out.push_str("  ");
+        }
+        write(out, child, indent + 1, action);
+    }
+    out.push(')');
+}
+
+impl<A> Behavior<A> {
+    /// Parses a behavior from the text format.
+    ///
+    /// The closure parses actions and returns an error message if it fails.
+    ///
+    /// ```text
+    /// ; Comments start with `;`.
+    /// (sequence
+    ///   (wait 1.5)
+    ///   (action jump)
+    ///   (if (action "see enemy")
+    ///     (action attack)
+    ///     (wait-for-pressed (keyboard 97)))
+    ///   (while (wait-forever)
+    ///     (action patrol)))
+    /// ```
+    ///
+    /// Buttons are written `(keyboard code)`, `(mouse code)`,
+    /// `(controller id button)` or `(hat id which state)`.
+    ///
+    /// Composites may be empty, like `(sequence)`,
+    /// so everything printed by `to_text` is read back.
+    /// Use `Behavior::validate` to find empty composites that can not run.
+    /// Behaviors can be nested 256 levels deep.
+    pub fn parse<F>(text: &str, mut action: F) -> Result<Behavior<A>, ParseError>
+    where
+        F: FnMut(&str) -> Result<A, String>,
+    {
+        let mut parser = Parser {
+            chars: text.chars().peekable(),
+            line: 1,
+            column: 1,
+            pos: (1, 1),
+            depth: 0,
+        };
+        if parser.token()? != Token::Open {
+            return parser.error("Expected `(`");
+        }
+        let behavior = parser.node(&mut action)?;
+        if parser.token()? != Token::End {
+            return parser.error("Expected end of text");
+        }
+        Ok(behavior)
+    }
+
+    /// Prints the behavior in the text format.
+    ///
+    /// The closure prints actions, which are quoted when necessary.
+    /// The output is parsed back to the same behavior by `Behavior::parse`.
+    pub fn to_text<F>(&self, mut action: F) -> String
+    where
+        F: FnMut(&A) -> String,
+    {
+        let mut out = String::new();
+        write(&mut out, self, 0, &mut action);
+        out.push('\n');
+        out
+    }
+}
@@ -0,0 +1,92 @@
+use std::fmt;
+
+use crate::{Action, Behavior, Select, Sequence, Wait, While};
+
+/// A problem found in a behavior by `Behavior::validate`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ValidationError {
+    /// The path of the node, as used by `State::active_paths`.
+    pub path: Vec<usize>,
+    /// Describes the problem.
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self.path, self.message)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Statistics about a behavior tree.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct Stats {
+    /// Number of nodes.
+    pub nodes: usize,
+    /// Length of the longest path from the root to a leaf, counting nodes.
+    pub depth: usize,
+    /// Number of `Action` nodes.
+    pub actions: usize,
+    /// Number of nodes without children.
+    pub leaves: usize,
+}
+
+impl<A> Behavior<A> {
+    /// Finds problems that make the behavior panic or misbehave at runtime.
+    ///
+    /// - `Select`, `Sequence` and the body of `While` must not be empty,
+    ///   because `State::new` panics.
+    /// - `Wait` must be a finite non-negative number of seconds.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = vec![];
+        self.collect_errors(&mut vec![], &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn collect_errors(&self, path: &mut Vec<usize>, errors: &mut Vec<ValidationError>) {
+        let mut error = |message: &str| {
+            errors.push(ValidationError {
+                path: path.clone(),
+                message: message.into(),
+            })
+        };
+        match *self {
+            Select(ref all) if all.is_empty() => error("`Select` has no behaviors"),
+            Sequence(ref all) if all.is_empty() => error("`Sequence` has no behaviors"),
+            While(_, ref rep) if rep.is_empty() => error("`While` has an empty body"),
+            Wait(dt) if !(dt.is_finite() && dt >= 0.0) => {
+                error("`Wait` must be finite and non-negative")
+            }
+            _ => {}
+        }
+        for (i, (_, child)) in self.children().into_iter().enumerate() {
+            path.push(i);
+            child.collect_errors(path, errors);
+            path.pop();
+        }
+    }
+
+    /// Computes statistics about the behavior tree.
+    pub fn stats(&self) -> Stats {
+        self.fold(|behavior, children: Vec<Stats>| {
+            let mut stats = Stats {
+                nodes: 1,
+                depth: 1,
+                actions: matches!(*behavior, Action(_)) as usize,
+                leaves: children.is_empty() as usize,
+            };
+            for child in children {
+                stats.nodes += child.nodes;
+                stats.depth = stats.depth.max(child.depth + 1);
+                stats.actions += child.actions;
+                stats.leaves += child.leaves;
+            }
+            stats
+        })
+    }
+}
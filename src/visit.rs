@@ -0,0 +1,189 @@
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt;
+
+use crate::{
+    Action, After, AlwaysSucceed, Behavior, Fail, If, Select, Sequence, Wait, WaitForPressed,
+    WaitForReleased, WaitForever, WhenAll, WhenAny, While,
+};
+
+/// An action that could not be converted by `Behavior::try_map`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MapError<E> {
+    /// The path of the action node, as used by `State::active_paths`.
+    pub path: Vec<usize>,
+    /// The error returned by the closure.
+    pub error: E,
+}
+
+impl<E: fmt::Display> fmt::Display for MapError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self.path, self.error)
+    }
+}
+
+impl<E: Error> Error for MapError<E> {}
+
+/// Visits the nodes of a behavior tree, in depth-first order.
+///
+/// Nodes are identified by their path,
+/// using the same numbering as `State::active_paths`.
+///
+/// All methods do nothing by default.
+pub trait Visitor<A> {
+    /// Called before the children of a node are visited.
+    fn enter(&mut self, _path: &[usize], _behavior: &Behavior<A>) {}
+    /// Called after the children of a node are visited.
+    fn exit(&mut self, _path: &[usize], _behavior: &Behavior<A>) {}
+}
+
+fn try_map_at<A, B, E, F>(
+    behavior: Behavior<A>,
+    f: &mut F,
+    path: &mut Vec<usize>,
+    errors: &mut Vec<MapError<E>>,
+) -> Option<Behavior<B>>
+where
+    F: FnMut(A) -> Result<B, E>,
+{
+    // Maps the child with index `i`.
+    let mut child = |i: usize, behavior: Behavior<A>, errors: &mut Vec<MapError<E>>| {
+        path.push(i);
+        let res = try_map_at(behavior, f, path, errors);
+        path.pop();
+        res
+    };
+    // Maps all children with indices starting at `offset`,
+    // visiting every child to collect all errors.
+    let mut list = |offset: usize, all: Vec<Behavior<A>>, errors: &mut Vec<MapError<E>>| {
+        let mut res = Some(Vec::with_capacity(all.len()));
+        for (i, behavior) in all.into_iter().enumerate() {
+            match (child(offset + i, behavior, errors), res.as_mut()) {
+                (Some(b), Some(list)) => list.push(b),
+                _ => res = None,
+            }
+        }
+        res
+    };
+    Some(match behavior {
+        WaitForPressed(button) => WaitForPressed(button),
+        WaitForReleased(button) => WaitForReleased(button),
+        Wait(dt) => Wait(dt),
+        WaitForever => WaitForever,
+        Action(a) => match f(a) {
+            Ok(b) => Action(b),
+            Err(error) => {
+                errors.push(MapError {
+                    path: path.clone(),
+                    error,
+                });
+                return None;
+            }
+        },
+        Fail(ev) => Fail(Box::new(list(0, vec![*ev], errors)?.pop()?)),
+        AlwaysSucceed(ev) => AlwaysSucceed(Box::new(list(0, vec![*ev], errors)?.pop()?)),
+        If(condition, success, failure) => {
+            let mut all = list(0, vec![*condition, *success, *failure], errors)?.into_iter();
+            If(
+                Box::new(all.next()?),
+                Box::new(all.next()?),
+                Box::new(all.next()?),
+            )
+        }
+        While(ev, rep) => {
+            let condition = list(0, vec![*ev], errors);
+            let rep = list(1, rep, errors);
+            While(Box::new(condition?.pop()?), rep?)
+        }
+        Select(all) => Select(list(0, all, errors)?),
+        Sequence(all) => Sequence(list(0, all, errors)?),
+        WhenAll(all) => WhenAll(list(0, all, errors)?),
+        WhenAny(all) => WhenAny(list(0, all, errors)?),
+        After(all) => After(list(0, all, errors)?),
+    })
+}
+
+impl<A> Behavior<A> {
+    /// Converts the actions of the behavior.
+    pub fn map<B, F>(self, mut f: F) -> Behavior<B>
+    where
+        F: FnMut(A) -> B,
+    {
+        match self.try_map(|a| Ok::<B, Infallible>(f(a))) {
+            Ok(behavior) => behavior,
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Converts the actions of the behavior, e.g. from names loaded from a file.
+    ///
+    /// Every action is converted, so all errors are returned,
+    /// each with the path of the action.
+    ///
+    /// ```
+    /// use ai_behavior::{Action, Sequence};
+    ///
+    /// let b = Sequence(vec![Action("jump"), Action("fly")]);
+    /// let errors = b
+    ///     .try_map(|name| match name {
+    ///         "jump" => Ok(1),
+    ///         _ => Err(format!("Unknown action `{}`", name)),
+    ///     })
+    ///     .unwrap_err();
+    /// assert_eq!(errors[0].to_string(), "[1]: Unknown action `fly`");
+    /// ```
+    pub fn try_map<B, E, F>(self, mut f: F) -> Result<Behavior<B>, Vec<MapError<E>>>
+    where
+        F: FnMut(A) -> Result<B, E>,
+    {
+        let mut errors = vec![];
+        match try_map_at(self, &mut f, &mut vec![], &mut errors) {
+            Some(behavior) if errors.is_empty() => Ok(behavior),
+            _ => Err(errors),
+        }
+    }
+
+    /// Visits every node in depth-first order.
+    pub fn visit<V: Visitor<A>>(&self, visitor: &mut V) {
+        self.visit_at(&mut vec![], visitor)
+    }
+
+    fn visit_at<V: Visitor<A>>(&self, path: &mut Vec<usize>, visitor: &mut V) {
+        visitor.enter(path, self);
+        for (i, (_, child)) in self.children().into_iter().enumerate() {
+            path.push(i);
+            child.visit_at(path, visitor);
+            path.pop();
+        }
+        visitor.exit(path, self);
+    }
+
+    /// Computes a value bottom-up,
+    /// calling the closure with each node and the values of its children.
+    ///
+    /// ```
+    /// use ai_behavior::{Action, Sequence, Wait};
+    ///
+    /// let b = Sequence(vec![Wait(1.0), Action(())]);
+    /// let nodes = b.fold(|_, children: Vec<usize>| 1 + children.iter().sum::<usize>());
+    /// assert_eq!(nodes, 3);
+    /// ```
+    pub fn fold<T, F>(&self, mut f: F) -> T
+    where
+        F: FnMut(&Behavior<A>, Vec<T>) -> T,
+    {
+        self.fold_with(&mut f)
+    }
+
+    fn fold_with<T, F>(&self, f: &mut F) -> T
+    where
+        F: FnMut(&Behavior<A>, Vec<T>) -> T,
+    {
+        let children = self
+            .children()
+            .into_iter()
+            .map(|(_, child)| child.fold_with(f))
+            .collect();
+        f(self, children)
+    }
+}
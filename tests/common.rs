@@ -0,0 +1,80 @@
+use std::fmt::Display;
+
+use ai_behavior::{ActionArgs, ActionHandler, Observer, Running, State, Status, Success, RUNNING};
+use input::{Event, UpdateArgs};
+#[cfg(feature = "fuzz")]
+use {
+    ai_behavior::FuzzCase,
+    arbitrary::{Arbitrary, Unstructured},
+    proptest::prelude::*,
+};
+
+use crate::test_events::TestActions::{self, Dec, Inc};
+
+// Labels the test actions in rendered behaviors.
+pub fn label(action: &TestActions) -> String {
+    match *action {
+        Inc => "Inc".into(),
+        Dec => "Dec".into(),
+    }
+}
+
+// `Inc` succeeds immediately, `Dec` keeps running.
+pub fn tick<O>(dt: f64, state: &mut State<TestActions, ()>, observer: &mut O)
+where
+    O: Observer<TestActions, ()>,
+{
+    let e: Event = UpdateArgs { dt }.into();
+    state.event_with_observer(
+        &e,
+        &mut |args| match *args.action {
+            Inc => (Success, args.dt),
+            Dec => RUNNING,
+        },
+        observer,
+    );
+}
+
+// Runs actions that count their ticks in the action state,
+// succeeding after the number of ticks,
+// and returns the names of the actions called with their count.
+pub fn count_ticks<A>(state: &mut State<A, u32>, dt: f64, ticks: u32) -> (Status, Vec<String>)
+where
+    A: Clone + Display,
+{
+    let e: Event = UpdateArgs { dt }.into();
+    let mut called = vec![];
+    let (status, _) = state.event(&e, &mut |args: ActionArgs<Event, A, u32>| {
+        let count = args.state.get_or_insert(0);
+        *count += 1;
+        called.push(format!("{} {}", args.action, count));
+        if *count == ticks {
+            (Success, args.dt)
+        } else {
+            (Running, 0.0)
+        }
+    });
+    (status, called)
+}
+
+// Records the actions that are halted, with their state.
+#[derive(Default)]
+pub struct Halts(pub Vec<(&'static str, Option<u32>)>);
+
+impl ActionHandler<Event, &'static str, u32> for Halts {
+    fn tick(&mut self, _args: ActionArgs<Event, &'static str, u32>) -> (Status, f64) {
+        unreachable!()
+    }
+
+    fn halt(&mut self, action: &&'static str, state: Option<u32>) {
+        self.0.push((action, state));
+    }
+}
+
+// Random behaviors with random events, for property tests.
+#[cfg(feature = "fuzz")]
+pub fn fuzz_cases() -> impl Strategy<Value = FuzzCase> {
+    proptest::collection::vec(any::<u8>(), 0..1024).prop_filter_map("too few bytes", |bytes| {
+        FuzzCase::arbitrary(&mut Unstructured::new(&bytes)).ok()
+    })
+}
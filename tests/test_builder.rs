@@ -0,0 +1,69 @@
+use ai_behavior::{
+    Action, After, AlwaysSucceed, Behavior, Fail, If, Select, Sequence, State, Success, Wait,
+    WaitForever, WhenAll, WhenAny, While,
+};
+use input::{Event, UpdateArgs};
+
+use crate::test_events::TestActions::{self, Dec, Inc};
+
+#[test]
+fn builder_sequence() {
+    let b = Behavior::sequence()
+        .then(Action(Inc))
+        .then_wait(1.0)
+        .then_action(Dec)
+        .build();
+    assert_eq!(b, Sequence(vec![Action(Inc), Wait(1.0), Action(Dec)]));
+}
+
+#[test]
+fn builder_nested() {
+    let b: Behavior<TestActions> = Behavior::repeat_while(Wait(10.0))
+        .then(Behavior::when_all().then_action(Inc).then(WaitForever))
+        .then(Behavior::if_else(
+            Action(Inc),
+            Action(Dec).inverted(),
+            Wait(1.0),
+        ))
+        .then(Action(Dec).always_succeed())
+        .build();
+    assert_eq!(
+        b,
+        While(
+            Box::new(Wait(10.0)),
+            vec![
+                WhenAll(vec![Action(Inc), WaitForever]),
+                If(
+                    Box::new(Action(Inc)),
+                    Box::new(Fail(Box::new(Action(Dec)))),
+                    Box::new(Wait(1.0)),
+                ),
+                AlwaysSucceed(Box::new(Action(Dec))),
+            ]
+        )
+    );
+}
+
+#[test]
+fn builder_empty() {
+    let e: Event = UpdateArgs { dt: 1.0 }.into();
+    let mut f = |_: ai_behavior::ActionArgs<Event, TestActions, ()>| (Success, 0.0);
+    let mut run = |b: Behavior<TestActions>| State::new(b).event(&e, &mut f);
+    assert_eq!(run(Behavior::sequence().build()), (Success, 1.0));
+    assert_eq!(run(Behavior::when_all().build()), (Success, 1.0));
+    assert_eq!(run(Behavior::after().build()), (Success, 1.0));
+    assert_eq!(run(Behavior::select().build()), (ai_behavior::Failure, 1.0));
+    assert_eq!(
+        run(Behavior::when_any().build()),
+        (ai_behavior::Failure, 1.0)
+    );
+    assert_eq!(Behavior::<TestActions>::when_any().build(), WhenAny(vec![]));
+    assert_eq!(Behavior::<TestActions>::after().build(), After(vec![]));
+    assert_eq!(Behavior::repeat_while(Action(Inc)).build(), Action(Inc));
+    assert_eq!(
+        Behavior::select()
+            .then_all(vec![Action(Inc), Action(Dec)])
+            .build(),
+        Select(vec![Action(Inc), Action(Dec)])
+    );
+}
@@ -0,0 +1,166 @@
+use std::fs;
+use std::process::{Command, Output};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ai_behavior"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Writes a file to a directory of its own for the test and process,
+/// so tests running at the same time do not share files.
+fn write(test: &str, name: &str, contents: &str) -> String {
+    let dir = std::env::temp_dir().join(format!(
+        "ai_behavior_test_cli_{}_{}",
+        test,
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    fs::write(&path, contents).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn cli_ron() {
+    let file = write(
+        "cli_ron",
+        "guard.ron",
+        r#"Sequence([Wait(1.0), If(Action("see"), Action("attack"), WaitForever)])"#,
+    );
+    let out = run(&["check", &file]);
+    assert!(out.status.success());
+
+    let out = run(&["stats", &file]);
+    assert_eq!(
+        String::from_utf8(out.stdout).unwrap(),
+        "nodes: 6\ndepth: 3\nactions: 2\nleaves: 4\n"
+    );
+
+    let out = run(&["print", &file]);
+    assert_eq!(
+        String::from_utf8(out.stdout).unwrap(),
+        "Sequence
+|-- Wait(1)
+`-- If
+    |-- condition: see
+    |-- success: attack
+    `-- failure: WaitForever
+"
+    );
+
+    let out = run(&["dot", &file]);
+    assert!(String::from_utf8(out.stdout)
+        .unwrap()
+        .starts_with("digraph behavior {"));
+}
+
+#[test]
+fn cli_json_and_text() {
+    let file = write(
+        "cli_json_and_text",
+        "patrol.json",
+        r#"{"Sequence": [{"Action": "walk"}]}"#,
+    );
+    let out = run(&["print", &file, "--format", "text"]);
+    let text = String::from_utf8(out.stdout).unwrap();
+    assert_eq!(text, "(sequence\n  (action walk))\n");
+
+    let file = write("cli_json_and_text", "patrol.bt", &text);
+    let out = run(&["mermaid", &file]);
+    assert!(String::from_utf8(out.stdout)
+        .unwrap()
+        .starts_with("flowchart TD"));
+}
+
+#[test]
+fn cli_invalid() {
+    let file = write("cli_invalid", "invalid.ron", "Select([Wait(-1.0)])");
+    let out = run(&["check", &file]);
+    assert_eq!(out.status.code(), Some(1));
+    let err = String::from_utf8(out.stderr).unwrap();
+    assert!(err.contains("[0]: `Wait` must be finite and non-negative"));
+
+    let file = write("cli_invalid", "broken.bt", "(sequence (wiat 1))");
+    let out = run(&["print", &file]);
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8(out.stderr)
+        .unwrap()
+        .contains("1:12: Unknown behavior `wiat`"));
+
+    let out = run(&["frobnicate"]);
+    assert_eq!(out.status.code(), Some(2));
+}
+
+#[test]
+fn cli_simulate() {
+    let file = write(
+        "cli_simulate",
+        "guard.bt",
+        "(sequence (wait 1) (while (wait-for-pressed (keyboard 32)) (action Attack)))",
+    );
+    let scenario = write(
+        "cli_simulate",
+        "guard_scenario.ron",
+        r#"(
+            events: [Update(1.0), Update(2.5), Press(Keyboard(Space)), Update(1.0)],
+            actions: {"Attack": (status: Success, after: 2.0)},
+        )"#,
+    );
+    let out = run(&["simulate", &file, &scenario]);
+    assert!(out.status.success());
+    assert_eq!(
+        String::from_utf8(out.stdout).unwrap(),
+        "Update(1.0) (t = 1)
+  start [] Sequence
+  start [0] Wait(1)
+  success [0] Wait(1)
+  start [1] While
+  start [1, 0] WaitForPressed(Keyboard(Space))
+  start [1, 1] Attack
+Update(2.5) (t = 3.5)
+  success [1, 1] Attack
+  start [1, 1] Attack
+Press(Keyboard(Space)) (t = 3.5)
+  success [1, 0] WaitForPressed(Keyboard(Space))
+  abort [1, 1] Attack
+  success [1] While
+  success [] Sequence
+Success (t = 3.5)
+"
+    );
+
+    let out = run(&["simulate", &file, &file]);
+    assert_eq!(out.status.code(), Some(1));
+
+    let empty = write("cli_simulate", "empty.bt", "(sequence)");
+    let out = run(&["simulate", &empty, &scenario]);
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8(out.stderr)
+        .unwrap()
+        .contains("[]: `Sequence` has no behaviors"));
+}
+
+#[test]
+fn cli_diff() {
+    let old = write(
+        "cli_diff",
+        "old.bt",
+        "(sequence (action aim) (wait 1) (action fire))",
+    );
+    let new = write(
+        "cli_diff",
+        "new.ron",
+        r#"Sequence([Wait(2.0), Action("fire"), Action("aim")])"#,
+    );
+    let out = run(&["diff", &old, &new]);
+    assert_eq!(out.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(out.stdout).unwrap(),
+        "~ [1] -> [0] Wait(1) => Wait(2)\n> [0] -> [2] \"aim\"\n"
+    );
+    let out = run(&["diff", &new, &new]);
+    assert!(out.status.success());
+    assert!(out.stdout.is_empty());
+}
@@ -0,0 +1,54 @@
+use ai_behavior::{Action, Coverage, Failure, If, Select, State, Success, Wait};
+use input::{Event, UpdateArgs};
+
+use crate::test_events::TestActions::{self, Dec, Inc};
+
+fn tree() -> ai_behavior::Behavior<TestActions> {
+    If(
+        Box::new(Action(Inc)),
+        Box::new(Select(vec![Action(Dec), Wait(1.0)])),
+        Box::new(Action(Dec)),
+    )
+}
+
+fn run(inc: ai_behavior::Status, coverage: &mut Coverage) {
+    let mut state: State<_, ()> = State::new(tree());
+    let e: Event = UpdateArgs { dt: 0.5 }.into();
+    state.event_with_observer(
+        &e,
+        &mut |args| match *args.action {
+            Inc => (inc, args.dt),
+            Dec => (Failure, args.dt),
+        },
+        coverage,
+    );
+}
+
+#[test]
+fn coverage_report() {
+    let mut coverage = Coverage::new();
+    run(Success, &mut coverage);
+    let report = coverage.report(&tree());
+    let uncovered: Vec<_> = report.uncovered().map(|node| node.path.clone()).collect();
+    // `If` and `Inc` never fail, `Dec` never succeeds,
+    // `Select` and `Wait` never terminate and the failure branch is never hit.
+    assert_eq!(
+        uncovered,
+        vec![vec![], vec![0], vec![1], vec![1, 0], vec![1, 1], vec![2]]
+    );
+    assert_eq!(report.node_coverage(), 5.0 / 6.0);
+
+    run(Failure, &mut coverage);
+    let report = coverage.report(&tree());
+    assert_eq!(report.node_coverage(), 1.0);
+    assert!(report.outcome_coverage() < 1.0);
+    report.assert_min(0.4);
+}
+
+#[test]
+#[should_panic(expected = "Coverage is below 0.9")]
+fn coverage_assert_min() {
+    let mut coverage = Coverage::new();
+    run(Success, &mut coverage);
+    coverage.report(&tree()).assert_min(0.9);
+}
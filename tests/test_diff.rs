@@ -0,0 +1,73 @@
+use ai_behavior::{
+    Action, Behavior, If, Select, Sequence, TreeChange, Wait, WaitForever, WhenAll, While,
+};
+
+fn lines<A: PartialEq + std::fmt::Debug>(old: &Behavior<A>, new: &Behavior<A>) -> Vec<String> {
+    old.diff(new)
+        .changes
+        .iter()
+        .map(|c| c.to_string())
+        .collect()
+}
+
+#[test]
+fn diff_equal() {
+    let b = Sequence(vec![Wait(1.0), Action(1)]);
+    assert!(b.diff(&b.clone()).is_empty());
+}
+
+#[test]
+fn diff_insert_remove() {
+    let old = Sequence(vec![Action(1), Action(2), Action(3)]);
+    let new = Sequence(vec![Action(1), Action(3), WaitForever, Action(4)]);
+    // Nodes on different sides of an unchanged node are not paired.
+    assert_eq!(
+        lines(&old, &new),
+        vec!["- [1] 2", "+ [2] WaitForever", "+ [3] 4"]
+    );
+    assert_eq!(
+        old.diff(&new).changes[0],
+        TreeChange::Removed {
+            from: vec![1],
+            label: "2".into(),
+        }
+    );
+}
+
+#[test]
+fn diff_nested_change() {
+    let old = If(
+        Box::new(Action("see")),
+        Box::new(Sequence(vec![Action("aim"), Action("fire")])),
+        Box::new(While(Box::new(WaitForever), vec![Action("patrol")])),
+    );
+    let new = If(
+        Box::new(Action("hear")),
+        Box::new(Select(vec![
+            Action("aim"),
+            Action("fire"),
+            Action("reload"),
+        ])),
+        Box::new(While(
+            Box::new(WaitForever),
+            vec![Wait(1.0), Action("patrol")],
+        )),
+    );
+    assert_eq!(
+        lines(&old, &new),
+        vec![
+            "~ [0] -> [0] \"see\" => \"hear\"",
+            "~ [1] -> [1] Sequence => Select",
+            "+ [1, 2] \"reload\"",
+            "+ [2, 1] Wait(1)",
+        ]
+    );
+}
+
+#[test]
+fn diff_moved() {
+    let a = Sequence(vec![Action(1), Action(2)]);
+    let old = WhenAll(vec![a.clone(), Wait(1.0), Wait(2.0)]);
+    let new = WhenAll(vec![Wait(1.0), Wait(2.0), a]);
+    assert_eq!(lines(&old, &new), vec!["> [0] -> [2] Sequence"]);
+}
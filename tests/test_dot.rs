@@ -0,0 +1,68 @@
+use ai_behavior::{Action, Sequence, State, Wait, WhenAll};
+use input::{Event, UpdateArgs};
+
+use crate::common::label;
+use crate::test_events::TestActions::{self, Dec, Inc};
+
+#[test]
+fn behavior_dot() {
+    let seq = Sequence(vec![Wait(1.0), Action(Inc)]);
+    assert_eq!(
+        seq.to_dot(label),
+        "digraph behavior {
+    node [shape=box];
+    n0 [label=\"Sequence\"];
+    n1 [label=\"Wait(1)\"];
+    n0 -> n1;
+    n2 [label=\"Inc\"];
+    n0 -> n2;
+}
+"
+    );
+}
+
+#[test]
+fn state_dot() {
+    let seq = Sequence(vec![
+        Action(Inc),
+        WhenAll(vec![Wait(1.0), Action(Dec)]),
+        Action(Inc),
+    ]);
+    let mut state: State<TestActions, ()> = State::new(seq);
+    let e: Event = UpdateArgs { dt: 0.5 }.into();
+    state.event(&e, &mut |args| match *args.action {
+        Inc => (ai_behavior::Success, args.dt),
+        Dec => ai_behavior::RUNNING,
+    });
+    assert_eq!(
+        state.to_dot(label),
+        "digraph behavior {
+    node [shape=box];
+    n0 [label=\"Sequence\", style=\"filled,bold\", fillcolor=gold];
+    n1 [label=\"Inc\", style=filled, fillcolor=palegreen];
+    n0 -> n1;
+    n2 [label=\"WhenAll\", style=\"filled,bold\", fillcolor=gold];
+    n3 [label=\"Wait(0.5/1)\", style=\"filled,bold\", fillcolor=gold];
+    n2 -> n3;
+    n4 [label=\"Dec\", style=\"filled,bold\", fillcolor=gold];
+    n2 -> n4;
+    n0 -> n2;
+    n5 [label=\"Inc\", style=dashed, fontcolor=gray40, color=gray40];
+    n0 -> n5;
+}
+"
+    );
+}
+
+#[test]
+fn dot_escape() {
+    let b = Action("say \"hi\"\nthen \\ wave");
+    assert_eq!(
+        b.to_dot(|a: &&str| a.to_string()),
+        "digraph behavior {
+    node [shape=box];
+    n0 [label=\"say \\\"hi\\\"\\nthen \\\\ wave\"];
+}
+"
+    );
+}
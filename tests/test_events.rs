@@ -1,10 +1,12 @@
-use ai_behavior::{Action, Sequence, State, Success, Wait, WaitForever, WhenAll, While};
-use input::{Event, UpdateArgs};
+use ai_behavior::{
+    Action, After, Sequence, State, Success, Wait, WaitForPressed, WaitForever, WhenAll, While,
+};
+use input::{Button, ButtonArgs, ButtonState, Event, Key, UpdateArgs};
 
 use crate::test_events::TestActions::{Dec, Inc};
 
 /// Some test actions.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 #[allow(dead_code)]
 pub enum TestActions {
     /// Increment accumulator.
@@ -146,3 +148,44 @@ fn while_wait_forever_sequence() {
     a = exec(a, 1.001, &mut state);
     assert_eq!(a, 2);
 }
+
+// A loop body that takes no time runs once per update
+// instead of looping forever.
+#[test]
+fn while_instant_body() {
+    let mut a: u32 = 0;
+    let w = While(Box::new(WaitForever), vec![Action(Inc), Action(Inc)]);
+    let mut state = State::new(w);
+    a = exec(a, 1.0, &mut state);
+    assert_eq!(a, 2);
+    a = exec(a, 0.0, &mut state);
+    assert_eq!(a, 4);
+}
+
+#[test]
+fn after_empty() {
+    let mut state: State<TestActions, ()> = State::new(After(vec![]));
+    let e: Event = UpdateArgs { dt: 0.5 }.into();
+    assert_eq!(state.event(&e, &mut |_| unreachable!()), (Success, 0.5));
+}
+
+// A child of the loop body that succeeds on an input event
+// lets the next child run on the following update.
+#[test]
+fn while_press_then_action() {
+    let a: u32 = 0;
+    let w = While(
+        Box::new(WaitForever),
+        vec![WaitForPressed(Button::Keyboard(Key::Space)), Action(Inc)],
+    );
+    let mut state = State::new(w);
+    let press: Event = ButtonArgs {
+        state: ButtonState::Press,
+        button: Button::Keyboard(Key::Space),
+        scancode: None,
+    }
+    .into();
+    state.event(&press, &mut |_| unreachable!());
+    let a = exec(a, 0.0, &mut state);
+    assert_eq!(a, 1);
+}
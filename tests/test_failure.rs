@@ -0,0 +1,118 @@
+use std::error::Error;
+
+use ai_behavior::{
+    Action, ActionArgs, After, Behavior, Fail, Failure, FailureReason, Running, Select, Sequence,
+    State, Status, Success, Trace, Wait, WhenAll, WhenAny,
+};
+use input::{Event, UpdateArgs};
+
+#[derive(Clone, PartialEq, Debug)]
+enum A {
+    Ok,
+    Wait,
+    Break(&'static str),
+    Fail,
+}
+
+fn run(behavior: Behavior<A>, dt: f64) -> Result<(Status, f64), FailureReason> {
+    let e: Event = UpdateArgs { dt }.into();
+    let mut state: State<A, ()> = State::new(behavior);
+    state.event_with_reason(
+        &e,
+        &mut |args: ActionArgs<Event, A, ()>| match *args.action {
+            A::Ok => (Success, args.dt),
+            A::Wait => (Running, 0.0),
+            A::Break(reason) => args.fail(reason),
+            A::Fail => (Failure, args.dt),
+        },
+    )
+}
+
+fn path(res: Result<(Status, f64), FailureReason>) -> (Vec<usize>, Option<String>) {
+    let failure = res.unwrap_err();
+    (failure.path, failure.reason.map(|r| r.to_string()))
+}
+
+#[test]
+fn no_failure() {
+    assert_eq!(
+        run(Sequence(vec![Action(A::Ok)]), 1.0).unwrap(),
+        (Success, 1.0)
+    );
+    assert_eq!(run(Action(A::Wait), 1.0).unwrap(), (Running, 0.0));
+    // The reason is ignored when failures are turned into success.
+    assert_eq!(
+        run(Fail(Box::new(Action(A::Break("no")))), 1.0).unwrap(),
+        (Success, 1.0)
+    );
+}
+
+#[test]
+fn sequence_failure() {
+    let b = Sequence(vec![Wait(0.5), Action(A::Ok), Action(A::Break("locked"))]);
+    let failure = run(b, 1.0).unwrap_err();
+    assert_eq!(failure.path, vec![2]);
+    assert_eq!(failure.dt, 0.5);
+    assert_eq!(failure.to_string(), "[2] failed: locked");
+    assert_eq!(failure.source().unwrap().to_string(), "locked");
+}
+
+#[test]
+fn select_last_failure() {
+    let b = Select(vec![Action(A::Break("first")), Action(A::Break("second"))]);
+    assert_eq!(path(run(b, 1.0)), (vec![1], Some("second".into())));
+    let b = WhenAny(vec![Action(A::Break("first")), Action(A::Fail)]);
+    assert_eq!(path(run(b, 1.0)), (vec![1], None));
+}
+
+#[test]
+fn when_all_failure() {
+    let b = WhenAll(vec![
+        Action(A::Wait),
+        Sequence(vec![Action(A::Ok), Action(A::Break("hit"))]),
+    ]);
+    assert_eq!(path(run(b, 1.0)), (vec![1, 1], Some("hit".into())));
+}
+
+#[test]
+fn failure_without_reason() {
+    let failure = run(Fail(Box::new(Action(A::Ok))), 1.0).unwrap_err();
+    assert_eq!(failure.path, Vec::<usize>::new());
+    assert_eq!(failure.to_string(), "[] failed");
+    assert!(failure.source().is_none());
+    // The second wait succeeds first.
+    let b = After(vec![Wait(1.0), Wait(0.5)]);
+    assert_eq!(path(run(b, 1.0)), (vec![], None));
+}
+
+#[test]
+fn handle_with_reason() {
+    let e: Event = UpdateArgs { dt: 1.0 }.into();
+    let mut state: State<A, ()> = State::new(Sequence(vec![Action(A::Ok), Action(A::Break("no"))]));
+    let mut trace = Trace::new();
+    let failure = state
+        .handle_with_reason(
+            &e,
+            &mut |args: ActionArgs<Event, A, ()>| match *args.action {
+                A::Break(reason) => args.fail(reason),
+                _ => (Success, args.dt),
+            },
+            &mut trace,
+        )
+        .unwrap_err();
+    assert_eq!(failure.to_string(), "[1] failed: no");
+    assert_eq!(trace.exits().last(), Some((&[][..], Failure, 1.0)));
+}
+
+#[test]
+fn action_args_new() {
+    let (mut state, mut reason) = (None::<()>, None);
+    let args = ActionArgs::new(&(), 0.5, &A::Ok, &mut state, &mut reason);
+    assert_eq!(args.fail("no"), (Failure, 0.5));
+    assert_eq!(reason.unwrap().to_string(), "no");
+
+    let (mut state, mut reason) = (None::<()>, None);
+    let mut args = ActionArgs::new(&(), 0.5, &A::Ok, &mut state, &mut reason);
+    args.set_reason("late");
+    assert_eq!(reason.unwrap().to_string(), "late");
+}
@@ -0,0 +1,119 @@
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+use ai_behavior::{
+    Action, ActionFuture, Failure, Running, Sequence, State, Status, Success, Wait, WhenAny,
+};
+use input::{Event, UpdateArgs};
+
+// A job that is ready when the shared status is set.
+struct Job {
+    status: Rc<Cell<Option<Status>>>,
+    waker: Rc<RefCell<Option<Waker>>>,
+}
+
+impl Future for Job {
+    type Output = Status;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Status> {
+        match self.status.get() {
+            Some(status) => Poll::Ready(status),
+            None => {
+                *self.waker.borrow_mut() = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+fn update(dt: f64) -> Event {
+    UpdateArgs { dt }.into()
+}
+
+#[test]
+fn future_action() {
+    let status = Rc::new(Cell::new(None));
+    let waker = Rc::new(RefCell::new(None));
+    let mut state: State<&str, Job> = State::new(Sequence(vec![Action("path"), Wait(1.0)]));
+    let mut created = 0;
+    let mut job = |_: &&str| {
+        created += 1;
+        Job {
+            status: status.clone(),
+            waker: waker.clone(),
+        }
+    };
+    assert_eq!(state.event_async(&update(0.5), &mut job), (Running, 0.0));
+    assert_eq!(state.event_async(&update(0.5), &mut job), (Running, 0.0));
+    status.set(Some(Success));
+    // The remaining time is passed on when the future is ready.
+    assert_eq!(state.event_async(&update(1.5), &mut job), (Success, 0.5));
+    assert_eq!(created, 1);
+    // Ready futures are dropped.
+    assert_eq!(Rc::strong_count(&status), 1);
+}
+
+#[test]
+fn future_dropped_on_abort() {
+    let status = Rc::new(Cell::new(None));
+    let waker = Rc::new(RefCell::new(None));
+    let mut state: State<&str, Job> = State::new(WhenAny(vec![Wait(1.0), Action("load")]));
+    let mut job = |_: &&str| Job {
+        status: status.clone(),
+        waker: waker.clone(),
+    };
+    state.event_async(&update(0.5), &mut job);
+    assert_eq!(Rc::strong_count(&status), 2);
+    assert_eq!(state.event_async(&update(0.5), &mut job), (Success, 0.0));
+    assert_eq!(Rc::strong_count(&status), 1);
+}
+
+#[test]
+fn future_waker() {
+    struct Count(AtomicUsize);
+
+    impl Wake for Count {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let count = Arc::new(Count(AtomicUsize::new(0)));
+    let status = Rc::new(Cell::new(None));
+    let waker = Rc::new(RefCell::new(None));
+    let mut state: State<&str, Job> = State::new(Action("load"));
+    let mut job = |_: &&str| Job {
+        status: status.clone(),
+        waker: waker.clone(),
+    };
+    let e = update(0.1);
+    state.event_async_with_waker(&e, &Waker::from(count.clone()), &mut job);
+    waker.borrow_mut().take().unwrap().wake();
+    assert_eq!(count.0.load(Ordering::SeqCst), 1);
+    status.set(Some(Failure));
+    assert_eq!(state.event_async(&e, &mut job), (Failure, 0.1));
+}
+
+#[test]
+fn boxed_futures() {
+    let mut state: State<u32, ActionFuture> = State::new(Sequence(vec![Action(1), Action(2)]));
+    let mut calls = vec![];
+    let e = update(1.0);
+    let res = state.event_async(&e, &mut |&n| {
+        calls.push(n);
+        Box::pin(async move {
+            if n == 1 {
+                Success
+            } else {
+                Failure
+            }
+        })
+    });
+    assert_eq!(res, (Failure, 1.0));
+    assert_eq!(calls, vec![1, 2]);
+}
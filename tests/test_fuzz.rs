@@ -0,0 +1,12 @@
+use proptest::prelude::*;
+
+use crate::common::fuzz_cases;
+
+proptest! {
+    #[test]
+    fn state_event_invariants(case in fuzz_cases()) {
+        if let Err(err) = case.check() {
+            return Err(TestCaseError::fail(format!("{}\n{:?}", err, case)));
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use ai_behavior::{
+    Action, ActionArgs, ActionHandler, AnyState, Running, Sequence, State, Status, Success, Trace,
+    Wait, WaitForPressed, WhenAny,
+};
+use input::{Button, ButtonArgs, ButtonState, Event, Key, UpdateArgs};
+
+// Records the calls to the hooks.
+#[derive(Default)]
+struct Log {
+    calls: Vec<String>,
+}
+
+impl ActionHandler<Event, &'static str, u32> for Log {
+    fn start(&mut self, action: &&'static str) -> Option<u32> {
+        self.calls.push(format!("start {}", action));
+        Some(0)
+    }
+
+    fn tick(&mut self, args: ActionArgs<Event, &'static str, u32>) -> (Status, f64) {
+        let ticks = args.state.as_mut().unwrap();
+        *ticks += 1;
+        self.calls.push(format!("tick {} {}", args.action, ticks));
+        if *args.action == "jump" {
+            (Success, args.dt)
+        } else {
+            (Running, 0.0)
+        }
+    }
+
+    fn halt(&mut self, action: &&'static str, state: Option<u32>) {
+        self.calls.push(format!("halt {} {:?}", action, state));
+    }
+}
+
+#[test]
+fn handler_hooks() {
+    let b = WhenAny(vec![
+        Wait(1.0),
+        Sequence(vec![Action("jump"), Action("run")]),
+    ]);
+    let mut state = State::new(b);
+    let mut log = Log::default();
+    let e: Event = UpdateArgs { dt: 0.5 }.into();
+    assert_eq!(state.handle(&e, &mut log), (Running, 0.0));
+    assert_eq!(
+        state.handle_with_observer(&e, &mut log, &mut Trace::new()),
+        (Success, 0.0)
+    );
+    assert_eq!(
+        log.calls,
+        vec![
+            "start jump",
+            "tick jump 1",
+            "start run",
+            "tick run 1",
+            "halt run Some(1)",
+        ]
+    );
+}
+
+#[test]
+fn start_every_run() {
+    // An action that ran to the end starts over when it runs again.
+    let mut state = State::new(Action("jump"));
+    let mut log = Log::default();
+    let e: Event = UpdateArgs { dt: 0.5 }.into();
+    for _ in 0..2 {
+        assert_eq!(state.handle(&e, &mut log), (Success, 0.5));
+    }
+    assert_eq!(
+        log.calls,
+        vec!["start jump", "tick jump 1", "start jump", "tick jump 1"]
+    );
+}
+
+#[test]
+fn closure_handler() {
+    // Closures keep working where a handler is expected.
+    let mut state: State<&str, ()> = State::new(Action("jump"));
+    let e: Event = UpdateArgs { dt: 0.5 }.into();
+    let res = state.handle(&e, &mut |args: ActionArgs<Event, &str, ()>| {
+        (Success, args.dt)
+    });
+    assert_eq!(res, (Success, 0.5));
+}
+
+// Counts the calls to the hooks, keeping actions running without state.
+#[derive(Default)]
+struct Stateless {
+    starts: usize,
+    ticks: usize,
+    halts: Vec<Option<()>>,
+}
+
+impl ActionHandler<Event, &'static str, ()> for Stateless {
+    fn start(&mut self, _action: &&'static str) -> Option<()> {
+        self.starts += 1;
+        None
+    }
+
+    fn tick(&mut self, _args: ActionArgs<Event, &'static str, ()>) -> (Status, f64) {
+        self.ticks += 1;
+        (Running, 0.0)
+    }
+
+    fn halt(&mut self, _action: &&'static str, state: Option<()>) {
+        self.halts.push(state);
+    }
+}
+
+#[test]
+fn start_once() {
+    // `start` is called once, even when it returns no state.
+    let mut state = State::new(WhenAny(vec![Wait(1.0), Action("run")]));
+    let mut handler = Stateless::default();
+    let e: Event = UpdateArgs { dt: 0.25 }.into();
+    state.handle(&e, &mut handler);
+    state.handle(&e, &mut handler);
+    assert_eq!((handler.starts, handler.ticks), (1, 2));
+    let e: Event = UpdateArgs { dt: 1.0 }.into();
+    assert_eq!(state.handle(&e, &mut handler), (Success, 0.5));
+    assert_eq!(handler.halts, vec![None]);
+}
+
+#[test]
+fn halt_only_started() {
+    let b = WhenAny(vec![
+        Wait(1.0),
+        Sequence(vec![
+            WaitForPressed(Button::Keyboard(Key::Space)),
+            Action("run"),
+        ]),
+    ]);
+    let mut state = State::new(b);
+    let mut handler = Stateless::default();
+    let press: Event = ButtonArgs {
+        state: ButtonState::Press,
+        button: Button::Keyboard(Key::Space),
+        scancode: None,
+    }
+    .into();
+    state.handle(&press, &mut handler);
+    // `run` is next, but the wait terminates before it starts.
+    let e: Event = UpdateArgs { dt: 1.0 }.into();
+    assert_eq!(state.handle(&e, &mut handler), (Success, 0.0));
+    assert_eq!((handler.starts, handler.ticks), (0, 0));
+    assert!(handler.halts.is_empty());
+}
+
+#[test]
+fn any_state() {
+    let (mut state, mut reason): (Option<AnyState>, _) = (None, None);
+    let mut args = ActionArgs::new(&(), 0.0, &"walk", &mut state, &mut reason);
+    assert!(args.state_mut::<f64>().is_none());
+    *args.state_or_insert_with(|| 1.0) += 0.5;
+    assert_eq!(args.state_mut::<f64>(), Some(&mut 1.5));
+    // A state of another type is replaced.
+    assert!(args.state_mut::<u32>().is_none());
+    assert_eq!(*args.state_or_insert_with(|| 7u32), 7);
+}
@@ -0,0 +1,68 @@
+use ai_behavior::{
+    behavior, Action, After, AlwaysSucceed, Fail, If, Select, Sequence, Wait, WaitForPressed,
+    WaitForReleased, WaitForever, WhenAll, WhenAny, While,
+};
+use input::{Button, Key};
+
+use crate::test_events::TestActions::{Dec, Inc};
+
+#[test]
+fn macro_leaves() {
+    assert_eq!(behavior! { action Inc }, Action(Inc));
+    assert_eq!(behavior! { wait 0.5 }, Wait::<()>(0.5));
+    assert_eq!(behavior! { wait_forever }, WaitForever::<()>);
+    assert_eq!(
+        behavior! { wait_for_pressed Button::Keyboard(Key::A) },
+        WaitForPressed::<()>(Button::Keyboard(Key::A))
+    );
+    assert_eq!(
+        behavior! { wait_for_released Button::Keyboard(Key::A) },
+        WaitForReleased::<()>(Button::Keyboard(Key::A))
+    );
+}
+
+#[test]
+fn macro_sequence() {
+    let b = behavior! {
+        sequence {
+            wait 1.0;
+            action Inc;
+        }
+    };
+    assert_eq!(b, Sequence(vec![Wait(1.0), Action(Inc)]));
+}
+
+#[test]
+fn macro_nested() {
+    let inner = Action(Dec);
+    let b = behavior! {
+        while { wait 50.0 } {
+            select {
+                fail { action Inc }
+                always_succeed { wait_forever }
+            }
+            if { action Inc } { when_all { action Inc; wait 1.0 } } else { behavior inner }
+            when_any { action Dec; }
+            after { wait 1.0; action Dec }
+        }
+    };
+    assert_eq!(
+        b,
+        While(
+            Box::new(Wait(50.0)),
+            vec![
+                Select(vec![
+                    Fail(Box::new(Action(Inc))),
+                    AlwaysSucceed(Box::new(WaitForever)),
+                ]),
+                If(
+                    Box::new(Action(Inc)),
+                    Box::new(WhenAll(vec![Action(Inc), Wait(1.0)])),
+                    Box::new(Action(Dec)),
+                ),
+                WhenAny(vec![Action(Dec)]),
+                After(vec![Wait(1.0), Action(Dec)]),
+            ]
+        )
+    );
+}
@@ -0,0 +1,41 @@
+use ai_behavior::{
+    Action, Failure, MockActions, Running, Select, Sequence, State, Success, Wait, WhenAll,
+};
+
+#[test]
+fn mock_queue() {
+    let mut mock = MockActions::new();
+    mock.respond("a", Running, 0.0).respond("a", Success, 0.5);
+    let mut state = State::new(Sequence(vec![Action("a"), Wait(1.0)]));
+    assert_eq!(mock.update(&mut state, 1.0), (Running, 0.0));
+    assert_eq!(mock.update(&mut state, 1.0), (Running, 0.0));
+    assert_eq!(mock.update(&mut state, 0.5), (Success, 0.0));
+    mock.assert_calls(&"a", 2);
+}
+
+#[test]
+fn mock_caps_dt() {
+    let mut mock = MockActions::new();
+    mock.respond("a", Success, 5.0);
+    let mut state = State::new(Action("a"));
+    assert_eq!(mock.update(&mut state, 0.5), (Success, 0.5));
+}
+
+#[test]
+fn mock_advance() {
+    let mut mock = MockActions::new();
+    mock.respond("b", Failure, 0.0);
+    let mut state = State::new(Select(vec![Action("b"), Action("c")]));
+    assert_eq!(mock.advance(&mut state, 0.1, 3), Running);
+    mock.assert_calls(&"b", 1);
+    mock.assert_calls(&"c", 3);
+
+    let mut state = State::new(WhenAll(vec![Wait(0.25), Wait(0.5)]));
+    assert_eq!(mock.advance(&mut state, 0.1, 10), Success);
+}
+
+#[test]
+#[should_panic(expected = "Expected \"a\" to be called 1 times, but it was called 0 times")]
+fn mock_assert_calls() {
+    MockActions::new().assert_calls(&"a", 1);
+}
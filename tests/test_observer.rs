@@ -0,0 +1,83 @@
+use ai_behavior::{
+    Action, Running, Sequence, State, Success, Trace, TraceEvent, Wait, WhenAny, While, RUNNING,
+};
+use input::{Event, UpdateArgs};
+
+use crate::common::tick;
+use crate::test_events::TestActions::{self, Dec, Inc};
+
+#[test]
+fn trace_sequence() {
+    let mut state = State::new(Sequence(vec![Action(Inc), Wait(1.0)]));
+    let mut trace = Trace::new();
+    tick(0.5, &mut state, &mut trace);
+    assert_eq!(
+        trace.events,
+        vec![
+            TraceEvent::Enter(vec![]),
+            TraceEvent::Enter(vec![0]),
+            TraceEvent::Exit(vec![0], Success, 0.5),
+            TraceEvent::Enter(vec![1]),
+            TraceEvent::Exit(vec![1], Running, 0.0),
+            TraceEvent::Exit(vec![], Running, 0.0),
+        ]
+    );
+}
+
+#[test]
+fn trace_abort() {
+    let mut state = State::new(WhenAny(vec![Action(Dec), Wait(1.0)]));
+    let mut trace = Trace::new();
+    tick(1.0, &mut state, &mut trace);
+    assert_eq!(
+        trace.events,
+        vec![
+            TraceEvent::Enter(vec![]),
+            TraceEvent::Enter(vec![0]),
+            TraceEvent::Exit(vec![0], Running, 0.0),
+            TraceEvent::Enter(vec![1]),
+            TraceEvent::Exit(vec![1], Success, 0.0),
+            TraceEvent::Abort(vec![0]),
+            TraceEvent::Exit(vec![], Success, 0.0),
+        ]
+    );
+}
+
+#[test]
+fn trace_while_condition_aborts_body() {
+    let b = While(Box::new(Wait(1.0)), vec![Sequence(vec![Action(Dec)])]);
+    let mut state = State::new(b);
+    let mut trace = Trace::new();
+    tick(0.5, &mut state, &mut trace);
+    trace.events.clear();
+    tick(0.5, &mut state, &mut trace);
+    let aborts: Vec<_> = trace
+        .events
+        .iter()
+        .filter(|ev| matches!(ev, TraceEvent::Abort(_)))
+        .cloned()
+        .collect();
+    assert_eq!(
+        aborts,
+        vec![TraceEvent::Abort(vec![1, 0]), TraceEvent::Abort(vec![1])]
+    );
+    assert_eq!(trace.exits().last(), Some((&[][..], Success, 0.0)));
+}
+
+// Records the delta time given to each node.
+struct Given(Vec<(Vec<usize>, f64)>);
+
+impl<A, S> ai_behavior::Observer<A, S> for Given {
+    fn update(&mut self, path: &[usize], dt: f64) {
+        self.0.push((path.to_vec(), dt));
+    }
+}
+
+#[test]
+fn observer_update_dt() {
+    let mut state: State<TestActions, ()> = State::new(Sequence(vec![Wait(0.5), Wait(1.0)]));
+    let mut given = Given(vec![]);
+    let e: Event = UpdateArgs { dt: 2.0 }.into();
+    state.event_with_observer(&e, &mut |_| RUNNING, &mut given);
+    assert_eq!(given.0, vec![(vec![], 2.0), (vec![0], 2.0), (vec![1], 1.5)]);
+}
@@ -0,0 +1,127 @@
+use ai_behavior::{
+    Action, ActionArgs, After, AlwaysSucceed, Fail, If, Running, Select, Sequence, State, Wait,
+    WaitForever, WhenAll, While,
+};
+use input::{Event, UpdateArgs};
+
+#[cfg(feature = "fuzz")]
+use {
+    crate::common::fuzz_cases,
+    ai_behavior::{Behavior, FuzzCase, RecordedEvent, Status},
+    proptest::prelude::*,
+};
+
+#[test]
+fn flatten() {
+    let b = Sequence(vec![
+        Sequence(vec![Action(1), Action(2)]),
+        Action(3),
+        Sequence(vec![Action(4), Sequence(vec![Action(5), Action(6)])]),
+    ]);
+    assert_eq!(b.optimize(), Sequence((1..7).map(Action).collect()));
+
+    let b = Select(vec![
+        Select(vec![Action(1), Action(2)]),
+        Sequence(vec![Action(3)]),
+    ]);
+    assert_eq!(b.optimize(), Select(vec![Action(1), Action(2), Action(3)]));
+
+    // Different composites are not flattened.
+    let b = Sequence(vec![Select(vec![Action(1), Action(2)]), Action(3)]);
+    assert_eq!(b.clone().optimize(), b);
+}
+
+#[test]
+fn inversions() {
+    let b = Fail(Box::new(Fail(Box::new(Fail(Box::new(Action(1)))))));
+    assert_eq!(b.optimize(), Fail(Box::new(Action(1))));
+    let b = AlwaysSucceed(Box::new(Fail(Box::new(AlwaysSucceed(Box::new(Action(1)))))));
+    assert_eq!(b.optimize(), AlwaysSucceed(Box::new(Action(1))));
+}
+
+#[test]
+fn single_child() {
+    let b = If(
+        Box::new(WhenAll(vec![Action(1)])),
+        Box::new(After(vec![Select(vec![Action(2)])])),
+        Box::new(While(
+            Box::new(WaitForever),
+            vec![Sequence(vec![Action(3)])],
+        )),
+    );
+    assert_eq!(
+        b.optimize(),
+        If(
+            Box::new(Action(1)),
+            Box::new(Action(2)),
+            Box::new(While(Box::new(WaitForever), vec![Action(3)])),
+        )
+    );
+}
+
+#[test]
+fn zero_waits() {
+    let b = Sequence(vec![
+        Wait(0.0),
+        Wait(1.0),
+        Wait(0.0),
+        Action(1),
+        Wait(0.0),
+        Sequence(vec![Wait(0.0), Action(2)]),
+    ]);
+    assert_eq!(
+        b.optimize(),
+        Sequence(vec![Wait(1.0), Action(1), Wait(0.0), Action(2)])
+    );
+    // A zero wait delays input events until the next update.
+    let b: ai_behavior::Behavior<()> = WhenAll(vec![Wait(0.0), WaitForever]);
+    assert_eq!(b.clone().optimize(), b);
+}
+
+#[test]
+fn running_dt() {
+    // Only the delta time left when the behavior terminates is the same.
+    let e: Event = UpdateArgs { dt: 1.0 }.into();
+    let mut f = |args: ActionArgs<Event, u8, ()>| (Running, args.dt);
+    let b = Sequence(vec![Action(1)]);
+    assert_eq!(State::new(b.clone()).event(&e, &mut f), (Running, 0.0));
+    assert_eq!(State::new(b.optimize()).event(&e, &mut f), (Running, 1.0));
+}
+
+// Runs the events and returns the result of each event and the actions called.
+// Actions return delta time while running too,
+// but only the delta time of a terminated behavior is kept.
+#[cfg(feature = "fuzz")]
+fn run(behavior: Behavior<u8>, events: &[RecordedEvent]) -> Vec<((Status, f64), Vec<u8>)> {
+    let mut state: State<u8, ()> = State::new(behavior);
+    let mut res = vec![];
+    for recorded in events {
+        let e: Event = recorded.clone().into();
+        let mut actions = vec![];
+        let (status, dt) = state.event(&e, &mut |args| {
+            actions.push(*args.action);
+            FuzzCase::run_action(args)
+        });
+        let dt = if status == Running { 0.0 } else { dt };
+        res.push(((status, dt), actions));
+        if status != Running {
+            break;
+        }
+    }
+    res
+}
+
+#[cfg(feature = "fuzz")]
+proptest! {
+    #[test]
+    fn optimize_preserves_semantics(case in fuzz_cases()) {
+        let optimized = case.behavior.clone().optimize();
+        prop_assert_eq!(
+            run(case.behavior.clone(), &case.events),
+            run(optimized.clone(), &case.events),
+            "{:?}\n{:?}",
+            case,
+            optimized
+        );
+    }
+}
@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use ai_behavior::{Action, Failure, Profiler, Select, State, Wait, While, RUNNING};
+use input::{Event, UpdateArgs};
+
+use crate::test_events::TestActions::{Dec, Inc};
+
+#[test]
+fn profile_counters() {
+    let b = While(
+        Box::new(Wait(1.0)),
+        vec![Select(vec![Action(Dec), Action(Inc)])],
+    );
+    let mut profiler = Profiler::new();
+    // Several agents share the profiler.
+    for _ in 0..2 {
+        let mut state: State<_, ()> = State::new(b.clone());
+        for _ in 0..3 {
+            let e: Event = UpdateArgs { dt: 0.25 }.into();
+            state.event_with_observer(
+                &e,
+                &mut |args| match *args.action {
+                    Inc => {
+                        std::thread::sleep(Duration::from_millis(1));
+                        RUNNING
+                    }
+                    Dec => (Failure, args.dt),
+                },
+                &mut profiler,
+            );
+        }
+    }
+    let root = profiler.get(&[]).unwrap();
+    assert_eq!(root.ticks, 6);
+    assert_eq!(root.running, 6);
+    assert_eq!(root.actions, 0);
+    // `Dec` fails once per agent, then `Inc` keeps running.
+    let dec = profiler.get(&[1, 0]).unwrap();
+    assert_eq!((dec.actions, dec.failure), (2, 2));
+    let inc = profiler.get(&[1, 1]).unwrap();
+    assert_eq!((inc.actions, inc.running, inc.success), (6, 6, 0));
+    assert!(inc.time >= Duration::from_millis(6));
+    assert!(profiler.subtree_time(&[1]) >= inc.time);
+    assert_eq!(profiler.subtree_time(&[0]), Duration::from_secs(0));
+    assert_eq!(profiler.to_string().lines().count(), 6);
+}
@@ -0,0 +1,149 @@
+use ai_behavior::{
+    Action, After, Behavior, If, Sequence, State, Success, Wait, WaitForever, WhenAll, While,
+};
+use input::{Event, UpdateArgs};
+
+use crate::common::{count_ticks, Halts};
+
+#[cfg(feature = "fuzz")]
+use {crate::common::fuzz_cases, ai_behavior::FuzzCase, proptest::prelude::*};
+
+#[test]
+fn reload_wait() {
+    let mut state = State::new(Sequence(vec![Wait(2.0), Action("jump")]));
+    count_ticks(&mut state, 1.5, 3);
+    state.reload(Sequence(vec![Wait(3.0), Action("jump")]));
+    assert_eq!(count_ticks(&mut state, 1.0, 3).1, Vec::<String>::new());
+    assert_eq!(count_ticks(&mut state, 0.5, 3).1, vec!["jump 1"]);
+}
+
+#[test]
+fn reload_wait_shorter() {
+    let mut state: State<&str, u32> = State::new(Wait(2.0));
+    count_ticks(&mut state, 1.5, 3);
+    // The elapsed time is capped, so no time is left over.
+    state.reload(Wait(1.0));
+    let e: Event = UpdateArgs { dt: 0.0 }.into();
+    assert_eq!(state.event(&e, &mut |_| unreachable!()), (Success, 0.0));
+}
+
+#[test]
+fn reload_action_state() {
+    let b = WhenAll(vec![Action("run"), Action("look")]);
+    let mut state = State::new(b);
+    count_ticks(&mut state, 0.1, 3);
+    state.reload(WhenAll(vec![Action("run"), Action("listen"), WaitForever]));
+    // `run` keeps its state and `listen` starts over.
+    assert_eq!(count_ticks(&mut state, 0.1, 3).1, vec!["run 2", "listen 1"]);
+}
+
+#[test]
+fn reload_sequence_index() {
+    let mut state = State::new(Sequence(vec![Wait(1.0), Action("aim"), Action("fire")]));
+    count_ticks(&mut state, 1.0, 3);
+    count_ticks(&mut state, 0.1, 3);
+    // The running child is still at index 1.
+    state.reload(Sequence(vec![Wait(5.0), Action("aim"), Action("reload")]));
+    assert_eq!(count_ticks(&mut state, 0.1, 3).1, vec!["aim 3", "reload 1"]);
+
+    // The running child no longer exists, so the sequence starts over.
+    state.reload(Sequence(vec![Action("aim")]));
+    assert_eq!(count_ticks(&mut state, 0.1, 3).1, vec!["aim 1"]);
+}
+
+#[test]
+fn reload_changed_kind() {
+    let b: Behavior<&str> = If(
+        Box::new(Wait(1.0)),
+        Box::new(Action("attack")),
+        Box::new(WaitForever),
+    );
+    let mut state = State::new(b);
+    count_ticks(&mut state, 1.0, 3);
+    count_ticks(&mut state, 0.1, 3);
+    // The success branch changed from an action to a sequence.
+    state.reload(If(
+        Box::new(Wait(1.0)),
+        Box::new(Sequence(vec![Action("attack")])),
+        Box::new(WaitForever),
+    ));
+    assert_eq!(count_ticks(&mut state, 0.1, 3).1, vec!["attack 1"]);
+}
+
+#[test]
+fn reload_shrink_parallel() {
+    let e: Event = UpdateArgs { dt: 0.5 }.into();
+    let mut state: State<&str, u32> = State::new(WhenAll(vec![Wait(1.0), WaitForever]));
+    count_ticks(&mut state, 1.0, 3);
+    // The running child is removed, so the rest terminated.
+    state.reload(WhenAll(vec![Wait(1.0)]));
+    assert_eq!(state.event(&e, &mut |_| unreachable!()), (Success, 0.5));
+
+    let mut state: State<&str, u32> = State::new(After(vec![Wait(1.0), WaitForever]));
+    count_ticks(&mut state, 1.0, 3);
+    state.reload(After(vec![Wait(1.0)]));
+    assert_eq!(state.event(&e, &mut |_| unreachable!()), (Success, 0.5));
+}
+
+#[test]
+fn reload_changed_terminated() {
+    // A terminated child that changed runs again.
+    let mut state: State<&str, u32> = State::new(WhenAll(vec![Wait(1.0), WaitForever]));
+    count_ticks(&mut state, 1.0, 3);
+    assert_eq!(state.active_paths(), vec![vec![1]]);
+    state.reload(WhenAll(vec![Wait(2.0), WaitForever]));
+    assert_eq!(state.active_paths(), vec![vec![0], vec![1]]);
+
+    // Children of `After` run again from the first that changed.
+    let mut state: State<&str, u32> = State::new(After(vec![Wait(1.0), Wait(2.0), WaitForever]));
+    count_ticks(&mut state, 2.0, 3);
+    assert_eq!(state.active_paths(), vec![vec![2]]);
+    state.reload(After(vec![Wait(1.0), Wait(3.0), WaitForever]));
+    assert_eq!(state.active_paths(), vec![vec![1], vec![2]]);
+    count_ticks(&mut state, 3.0, 3);
+    assert_eq!(state.active_paths(), vec![vec![2]]);
+
+    // Children that did not change stay terminated.
+    state.reload(After(vec![Wait(1.0), Wait(3.0), WaitForever, WaitForever]));
+    assert_eq!(state.active_paths(), vec![vec![2], vec![3]]);
+}
+
+#[test]
+fn reload_with_handler() {
+    let mut state = State::new(WhenAll(vec![Action("run"), Action("look"), Action("talk")]));
+    count_ticks(&mut state, 0.1, 3);
+    let mut halts = Halts::default();
+    state.reload_with_handler(WhenAll(vec![Action("run"), Action("listen")]), &mut halts);
+    assert_eq!(halts.0, vec![("look", Some(1)), ("talk", Some(1))]);
+
+    // Actions that terminated are not halted.
+    let mut state = State::new(Sequence(vec![Action("aim"), Action("fire")]));
+    for _ in 0..4 {
+        count_ticks(&mut state, 0.1, 3);
+    }
+    let mut halts = Halts::default();
+    state.reload_with_handler(Action("aim"), &mut halts);
+    assert_eq!(halts.0, vec![("fire", Some(2))]);
+}
+
+#[test]
+#[should_panic]
+fn reload_empty_while() {
+    let mut state: State<(), ()> = State::new(While(Box::new(WaitForever), vec![Wait(1.0)]));
+    state.reload(While(Box::new(WaitForever), vec![]));
+}
+
+#[cfg(feature = "fuzz")]
+proptest! {
+    #[test]
+    fn reload_same_behavior(case in fuzz_cases()) {
+        let mut state: State<u8, ()> = State::new(case.behavior.clone());
+        for recorded in &case.events {
+            let e: Event = recorded.clone().into();
+            state.event(&e, &mut FuzzCase::run_action);
+            let mut reloaded = state.clone();
+            reloaded.reload(case.behavior.clone());
+            prop_assert_eq!(&reloaded, &state);
+        }
+    }
+}
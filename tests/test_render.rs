@@ -0,0 +1,72 @@
+use ai_behavior::{Action, If, Sequence, Wait, While};
+
+use crate::common::label;
+use crate::test_events::TestActions::{Dec, Inc};
+
+#[test]
+fn ascii() {
+    let b = Sequence(vec![
+        Wait(1.0),
+        If(
+            Box::new(Action(Inc)),
+            Box::new(While(Box::new(Wait(2.0)), vec![Action(Dec)])),
+            Box::new(Action(Dec)),
+        ),
+    ]);
+    assert_eq!(
+        b.to_ascii(label),
+        "Sequence
+|-- Wait(1)
+`-- If
+    |-- condition: Inc
+    |-- success: While
+    |   |-- condition: Wait(2)
+    |   `-- body: Dec
+    `-- failure: Dec
+"
+    );
+}
+
+#[test]
+fn mermaid() {
+    let b = If(
+        Box::new(Action(Inc)),
+        Box::new(Sequence(vec![Wait(0.5), Action(Dec)])),
+        Box::new(Action(Dec)),
+    );
+    assert_eq!(
+        b.to_mermaid(label),
+        "flowchart TD
+    n0[\"If\"]
+    n1[\"Inc\"]
+    n0 -->|condition| n1
+    n2[\"Sequence\"]
+    n3[\"Wait(0.5)\"]
+    n2 --> n3
+    n4[\"Dec\"]
+    n2 --> n4
+    n0 -->|success| n2
+    n5[\"Dec\"]
+    n0 -->|failure| n5
+"
+    );
+}
+
+#[test]
+fn render_escape() {
+    let b = Sequence(vec![Action("say \"hi\" [#1|2]\nthen wave")]);
+    assert_eq!(
+        b.to_mermaid(|a: &&str| a.to_string()),
+        "flowchart TD
+    n0[\"Sequence\"]
+    n1[\"say #quot;hi#quot; #91;#35;1#124;2#93;<br>then wave\"]
+    n0 --> n1
+"
+    );
+    assert_eq!(
+        b.to_ascii(|a: &&str| a.to_string()),
+        "Sequence
+`-- say \"hi\" [#1|2]\\nthen wave
+"
+    );
+}
@@ -0,0 +1,53 @@
+use ai_behavior::{Action, Failure, Recording, Sequence, State, Success, Wait, WhenAny, RUNNING};
+use input::{Event, UpdateArgs};
+
+use crate::test_events::TestActions::{Dec, Inc};
+
+fn record() -> Recording {
+    let b = WhenAny(vec![Sequence(vec![Wait(1.0), Action(Inc)]), Action(Dec)]);
+    let mut state: State<_, u32> = State::new(b);
+    let mut recording = Recording::new();
+    for _ in 0..3 {
+        let e: Event = UpdateArgs { dt: 0.3 }.into();
+        recording.record(&mut state, &e, &mut |args| match *args.action {
+            Inc => (Success, args.dt),
+            // Fails the third time it is called.
+            Dec => {
+                let n = args.state.get_or_insert(0);
+                *n += 1;
+                if *n == 3 {
+                    (Failure, args.dt)
+                } else {
+                    RUNNING
+                }
+            }
+        });
+    }
+    recording
+}
+
+#[test]
+fn replay_identical() {
+    let recording = record();
+    assert_eq!(recording.frames.len(), 3);
+    assert_eq!(recording.frames[2].actions, vec![(Failure, 0.3)]);
+    let b = WhenAny(vec![Sequence(vec![Wait(1.0), Action(Inc)]), Action(Dec)]);
+    assert_eq!(recording.replay(b), Ok(()));
+}
+
+#[test]
+fn replay_serialized() {
+    let json = serde_json::to_string(&record()).unwrap();
+    let recording: Recording = serde_json::from_str(&json).unwrap();
+    let b = WhenAny(vec![Sequence(vec![Wait(1.0), Action(Inc)]), Action(Dec)]);
+    assert_eq!(recording.replay(b), Ok(()));
+}
+
+#[test]
+fn replay_diverged() {
+    let recording = record();
+    // The wait is shorter, so the sequence reaches `Inc` in the second frame.
+    let b = WhenAny(vec![Sequence(vec![Wait(0.5), Action(Inc)]), Action(Dec)]);
+    let err = recording.replay(b).unwrap_err();
+    assert_eq!(err.frame, 1);
+}
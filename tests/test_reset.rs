@@ -0,0 +1,93 @@
+use ai_behavior::{
+    Action, Behavior, If, Sequence, State, Trace, TraceEvent, Wait, WaitForever, WhenAll, While,
+};
+
+use crate::common::{count_ticks, Halts};
+
+fn guard() -> Behavior<&'static str> {
+    If(
+        Box::new(Action("look")),
+        Box::new(WhenAll(vec![Wait(0.25), Action("shout"), Wait(1.0)])),
+        Box::new(WaitForever),
+    )
+}
+
+#[test]
+fn behavior_of_state() {
+    let mut state = State::new(guard());
+    count_ticks(&mut state, 0.5, 2);
+    count_ticks(&mut state, 0.5, 2);
+    // The condition and the first wait terminated.
+    assert_eq!(state.active_paths(), vec![vec![1, 1], vec![1, 2]]);
+    assert_eq!(state.behavior(), guard());
+}
+
+#[test]
+fn reset() {
+    let mut state = State::new(guard());
+    for _ in 0..2 {
+        count_ticks(&mut state, 0.5, 2);
+    }
+    let mut trace = Trace::new();
+    state.reset_with_observer(&mut Halts::default(), &mut trace);
+    assert_eq!(
+        trace.events,
+        vec![
+            TraceEvent::Abort(vec![1, 1]),
+            TraceEvent::Abort(vec![1, 2]),
+            TraceEvent::Abort(vec![1]),
+            TraceEvent::Abort(vec![]),
+        ]
+    );
+    assert_eq!(state, State::new(guard()));
+    assert_eq!(count_ticks(&mut state, 0.5, 2).1, vec!["look 1"]);
+}
+
+#[test]
+fn restart_subtree() {
+    let b = While(
+        Box::new(WaitForever),
+        vec![Sequence(vec![Action("walk"), Action("look")])],
+    );
+    let mut state = State::new(b);
+    count_ticks(&mut state, 0.5, 2);
+    count_ticks(&mut state, 0.5, 2);
+    assert_eq!(state.active_paths(), vec![vec![0], vec![1, 1]]);
+
+    // Only running nodes can be restarted.
+    let mut halts = Halts::default();
+    assert!(!state.restart(&[1, 0], &mut halts));
+    assert!(!state.restart(&[2], &mut halts));
+
+    let mut trace = Trace::new();
+    assert!(state.restart_with_observer(&[1], &mut halts, &mut trace));
+    assert_eq!(
+        trace.events,
+        vec![TraceEvent::Abort(vec![1, 1]), TraceEvent::Abort(vec![1])]
+    );
+    assert_eq!(state.active_paths(), vec![vec![0], vec![1, 0]]);
+    assert_eq!(count_ticks(&mut state, 0.5, 2).1, vec!["walk 1"]);
+}
+
+#[test]
+fn reset_halts() {
+    let mut state = State::new(guard());
+    for _ in 0..2 {
+        count_ticks(&mut state, 0.5, 2);
+    }
+    let mut halts = Halts::default();
+    state.reset(&mut halts);
+    assert_eq!(halts.0, vec![("shout", Some(1))]);
+    assert_eq!(state, State::new(guard()));
+
+    let b = While(
+        Box::new(WaitForever),
+        vec![Sequence(vec![Action("walk"), Action("look")])],
+    );
+    let mut state = State::new(b);
+    count_ticks(&mut state, 0.5, 2);
+    count_ticks(&mut state, 0.5, 2);
+    let mut halts = Halts::default();
+    assert!(state.restart(&[1], &mut halts));
+    assert_eq!(halts.0, vec![("look", Some(1))]);
+}
@@ -0,0 +1,64 @@
+use ai_behavior::{
+    Action, Behavior, Restored, Save, SaveHeader, Sequence, State, Wait, WaitForever, SAVE_VERSION,
+};
+use serde_json::Value;
+
+use crate::common::count_ticks;
+
+fn guard(wait: f64) -> Behavior<String> {
+    Sequence(vec![Wait(wait), Action("attack".into()), WaitForever])
+}
+
+#[test]
+fn tree_hash() {
+    assert_eq!(guard(1.0).tree_hash(), guard(1.0).tree_hash());
+    assert_ne!(guard(1.0).tree_hash(), guard(2.0).tree_hash());
+    let a: Behavior<u8> = Sequence(vec![Action(1), Action(2)]);
+    let b = Sequence(vec![Action(2), Action(1)]);
+    assert_ne!(a.tree_hash(), b.tree_hash());
+    // The hash must not change between releases.
+    assert_eq!(Wait::<()>(1.0).tree_hash(), 0x0535_b6cf_4e7f_4df9);
+}
+
+#[test]
+fn save_round_trip() {
+    let mut state = State::new(guard(1.0));
+    count_ticks(&mut state, 0.5, 1);
+    let text = serde_json::to_string(&Save::new(&guard(1.0), state.clone())).unwrap();
+
+    let header: SaveHeader = serde_json::from_str(&text).unwrap();
+    assert_eq!(header.version, SAVE_VERSION);
+    assert_eq!(header.tree_hash, guard(1.0).tree_hash());
+
+    let save: Save<String, u32> = serde_json::from_str(&text).unwrap();
+    assert_eq!(save.header(), header);
+    let data: Value = serde_json::from_str(&text).unwrap();
+    let (loaded, restored) =
+        Save::<String, u32>::load(&data, guard(1.0), |_, _, _| unreachable!()).unwrap();
+    assert_eq!(restored, Restored::Loaded);
+    assert_eq!(loaded, state);
+}
+
+#[test]
+fn save_migrate() {
+    let mut state = State::new(guard(1.0));
+    count_ticks(&mut state, 0.5, 1);
+    let mut data = serde_json::to_value(Save::new(&guard(1.0), state)).unwrap();
+
+    // The wait keeps its elapsed time.
+    let (mut migrated, restored) =
+        Save::<String, u32>::load(&data, guard(2.0), Save::migrate).unwrap();
+    assert_eq!(restored, Restored::Migrated);
+    assert_eq!(count_ticks(&mut migrated, 1.5, 1).1, vec!["attack 1"]);
+
+    let (state, restored) =
+        Save::<String, u32>::load(&data, guard(2.0), |_, _, _| Ok(None)).unwrap();
+    assert_eq!(restored, Restored::Restarted);
+    assert_eq!(state, State::new(guard(2.0)));
+
+    // Saves of an unknown format are not read.
+    data["version"] = (SAVE_VERSION + 1).into();
+    data["state"] = Value::Null;
+    let (_, restored) = Save::<String, u32>::load(&data, guard(1.0), Save::migrate).unwrap();
+    assert_eq!(restored, Restored::Restarted);
+}
@@ -0,0 +1,145 @@
+use ai_behavior::{
+    behavior_schema, Action, After, AlwaysSucceed, Behavior, Fail, If, Select, Sequence, Wait,
+    WaitForPressed, WaitForReleased, WaitForever, WhenAll, WhenAny, While,
+};
+use input::{Button, ControllerButton, ControllerHat, HatState, Key, MouseButton};
+use serde_json::{json, Value};
+
+// Validates the subset of JSON Schema used by `behavior_schema`.
+fn valid(root: &Value, schema: &Value, value: &Value) -> bool {
+    if let Some(schema) = schema.as_bool() {
+        return schema;
+    }
+    if let Some(r) = schema.get("$ref") {
+        let name = r.as_str().unwrap().trim_start_matches("#/$defs/");
+        if !valid(root, &root["$defs"][name], value) {
+            return false;
+        }
+    }
+    if let Some(one_of) = schema.get("oneOf") {
+        let n = one_of
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|s| valid(root, s, value))
+            .count();
+        if n != 1 {
+            return false;
+        }
+    }
+    if let Some(c) = schema.get("const") {
+        if c != value {
+            return false;
+        }
+    }
+    if let Some(e) = schema.get("enum") {
+        if !e.as_array().unwrap().contains(value) {
+            return false;
+        }
+    }
+    if let Some(min) = schema.get("minimum") {
+        match value.as_f64() {
+            Some(x) if x >= min.as_f64().unwrap() => {}
+            _ => return false,
+        }
+    }
+    if let Some(max) = schema.get("maximum") {
+        match value.as_f64() {
+            Some(x) if x <= max.as_f64().unwrap() => {}
+            _ => return false,
+        }
+    }
+    match schema.get("type").and_then(|t| t.as_str()) {
+        None => true,
+        Some("number") => value.is_number(),
+        Some("integer") => value.is_u64() || value.is_i64(),
+        Some("string") => value.is_string(),
+        Some("object") => {
+            let obj = match value.as_object() {
+                Some(obj) => obj,
+                None => return false,
+            };
+            let props = schema["properties"].as_object().unwrap();
+            schema["required"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .all(|r| obj.contains_key(r.as_str().unwrap()))
+                && obj.iter().all(|(k, v)| match props.get(k) {
+                    Some(s) => valid(root, s, v),
+                    None => false,
+                })
+        }
+        Some("array") => {
+            let arr = match value.as_array() {
+                Some(arr) => arr,
+                None => return false,
+            };
+            let prefix = schema
+                .get("prefixItems")
+                .and_then(|p| p.as_array())
+                .cloned()
+                .unwrap_or_default();
+            let min = schema.get("minItems").and_then(|m| m.as_u64()).unwrap_or(0);
+            arr.len() as u64 >= min
+                && arr.iter().enumerate().all(|(i, v)| match prefix.get(i) {
+                    Some(s) => valid(root, s, v),
+                    None => valid(root, &schema["items"], v),
+                })
+        }
+        Some(t) => panic!("Unsupported type {}", t),
+    }
+}
+
+fn check(schema: &Value, b: &Behavior<String>) -> bool {
+    valid(schema, schema, &serde_json::to_value(b).unwrap())
+}
+
+#[test]
+fn schema_matches_serde() {
+    let schema = behavior_schema(json!({ "type": "string" }));
+    let b = While(
+        Box::new(WaitForever),
+        vec![
+            Select(vec![
+                Fail(Box::new(Action("a".into()))),
+                AlwaysSucceed(Box::new(Wait(1.5))),
+            ]),
+            If(
+                Box::new(WaitForPressed(Button::Keyboard(Key::LShift))),
+                Box::new(WaitForReleased(Button::Mouse(MouseButton::X1))),
+                Box::new(WaitForPressed(Button::Controller(ControllerButton {
+                    id: 2,
+                    button: 3,
+                }))),
+            ),
+            Sequence(vec![WaitForPressed(Button::Hat(ControllerHat {
+                id: 0,
+                state: HatState::RightDown,
+                which: 1,
+            }))]),
+            WhenAll(vec![]),
+            WhenAny(vec![Wait(0.0)]),
+            After(vec![Action("b".into())]),
+        ],
+    );
+    assert!(check(&schema, &b));
+}
+
+#[test]
+fn schema_rejects_invalid() {
+    let schema = behavior_schema(json!({ "type": "string" }));
+    let invalid = vec![
+        json!({ "Wait": "1" }),
+        json!({ "Action": 1 }),
+        json!({ "Sequence": [] }),
+        json!({ "If": [ "WaitForever", "WaitForever" ] }),
+        json!({ "While": [ "WaitForever", [] ] }),
+        json!({ "WaitForPressed": { "Keyboard": "NotAKey" } }),
+        json!({ "Wait": 1.0, "WaitForever": null }),
+        json!("Wait"),
+    ];
+    for value in &invalid {
+        assert!(!valid(&schema, &schema, value), "{}", value);
+    }
+}
@@ -0,0 +1,88 @@
+use ai_behavior::{
+    Action, Behavior, If, Select, Sequence, Snapshot, SnapshotError, State, Wait, WaitForever,
+    WhenAll, While,
+};
+
+use crate::common::count_ticks;
+
+#[cfg(feature = "fuzz")]
+use {crate::common::fuzz_cases, ai_behavior::FuzzCase, input::Event, proptest::prelude::*};
+
+fn patrol() -> Behavior<&'static str> {
+    While(
+        Box::new(WaitForever),
+        vec![
+            Sequence(vec![Wait(1.0), Action("walk"), Action("look")]),
+            If(
+                Box::new(Action("hear")),
+                Box::new(WhenAll(vec![Action("run"), Wait(2.0)])),
+                Box::new(Select(vec![Action("rest")])),
+            ),
+        ],
+    )
+}
+
+#[test]
+fn snapshot_round_trip() {
+    let mut state = State::new(patrol());
+    for _ in 0..12 {
+        count_ticks(&mut state, 0.5, 3);
+        let snapshot = state.snapshot();
+        assert_eq!(State::from_snapshot(patrol(), snapshot), Ok(state.clone()));
+    }
+}
+
+#[test]
+fn snapshot_is_compact() {
+    let mut state = State::new(patrol());
+    count_ticks(&mut state, 1.5, 3);
+    let snapshot = state.snapshot();
+    assert_eq!(
+        snapshot,
+        Snapshot::While(
+            Box::new(Snapshot::Leaf),
+            0,
+            Box::new(Snapshot::Composite(
+                1,
+                Box::new(Snapshot::Action(Some(1), true))
+            )),
+        )
+    );
+    let full = serde_json::to_string(&state).unwrap();
+    let compact = serde_json::to_string(&snapshot).unwrap();
+    assert!(compact.len() * 4 < full.len());
+    let snapshot: Snapshot<u32> = serde_json::from_str(&compact).unwrap();
+    assert_eq!(State::from_snapshot(patrol(), snapshot), Ok(state));
+}
+
+#[test]
+fn snapshot_mismatch() {
+    let state: State<&str, u32> = State::new(Sequence(vec![Wait(1.0), Action("jump")]));
+    let snapshot = state.snapshot();
+    assert_eq!(
+        State::from_snapshot(Sequence(vec![Action("jump")]), snapshot.clone()),
+        Err(SnapshotError { path: vec![0] })
+    );
+    assert_eq!(
+        State::from_snapshot(Action("jump"), snapshot)
+            .unwrap_err()
+            .to_string(),
+        "Snapshot does not match the behavior at []"
+    );
+    let index = Snapshot::Composite(2, Box::new(Snapshot::Action(None, false)));
+    assert!(State::<_, u32>::from_snapshot(Sequence(vec![Action("jump")]), index).is_err());
+}
+
+#[cfg(feature = "fuzz")]
+proptest! {
+    #[test]
+    fn snapshot_round_trip_any(case in fuzz_cases()) {
+        let mut state: State<u8, ()> = State::new(case.behavior.clone());
+        for recorded in &case.events {
+            let e: Event = recorded.clone().into();
+            state.event(&e, &mut FuzzCase::run_action);
+            let restored = State::from_snapshot(case.behavior.clone(), state.snapshot());
+            prop_assert_eq!(restored.as_ref(), Ok(&state));
+        }
+    }
+}
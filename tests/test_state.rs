@@ -0,0 +1,72 @@
+use ai_behavior::{Action, After, If, Sequence, State, Wait, WaitForever, WhenAll, While};
+
+use crate::common::tick;
+use crate::test_events::TestActions::{Dec, Inc};
+
+#[cfg(feature = "fuzz")]
+use {crate::common::fuzz_cases, ai_behavior::FuzzCase, input::Event, proptest::prelude::*};
+
+#[test]
+fn debug() {
+    let state: State<u8, u32> = State::new(Sequence(vec![Action(1), Wait(2.0)]));
+    assert_eq!(
+        format!("{:?}", state),
+        "SequenceState([Action(1), Wait(2.0)], 0, ActionState(1, None, false))"
+    );
+}
+
+#[test]
+fn active_paths_sequence() {
+    let mut state = State::new(Sequence(vec![Action(Inc), Wait(1.0), Action(Inc)]));
+    assert_eq!(state.active_paths(), vec![vec![0]]);
+    tick(0.5, &mut state, &mut ());
+    assert_eq!(state.active_paths(), vec![vec![1]]);
+}
+
+#[test]
+fn active_paths_parallel() {
+    let b = Sequence(vec![
+        Action(Inc),
+        WhenAll(vec![
+            Wait(1.0),
+            If(
+                Box::new(Action(Inc)),
+                Box::new(Action(Dec)),
+                Box::new(WaitForever),
+            ),
+            Action(Inc),
+        ]),
+    ]);
+    let mut state = State::new(b);
+    tick(0.5, &mut state, &mut ());
+    assert_eq!(state.active_paths(), vec![vec![1, 0], vec![1, 1, 1]]);
+}
+
+#[test]
+fn active_paths_while_after() {
+    let b = While(
+        Box::new(Wait(10.0)),
+        vec![
+            Action(Inc),
+            After(vec![Action(Inc), Action(Dec), Wait(1.0)]),
+        ],
+    );
+    let mut state = State::new(b);
+    tick(0.5, &mut state, &mut ());
+    assert_eq!(state.active_paths(), vec![vec![0], vec![2, 1], vec![2, 2]]);
+}
+
+#[cfg(feature = "fuzz")]
+proptest! {
+    #[test]
+    fn behavior_of_state(case in fuzz_cases()) {
+        let mut state: State<u8, ()> = State::new(case.behavior.clone());
+        for recorded in &case.events {
+            let e: Event = recorded.clone().into();
+            state.event(&e, &mut FuzzCase::run_action);
+            prop_assert_eq!(&state.behavior(), &case.behavior);
+        }
+        state.reset(&mut FuzzCase::run_action::<Event>);
+        prop_assert_eq!(state, State::new(case.behavior));
+    }
+}
@@ -0,0 +1,101 @@
+use ai_behavior::{
+    Action, After, AlwaysSucceed, Behavior, Fail, If, Select, Sequence, Wait, WaitForPressed,
+    WaitForReleased, WaitForever, WhenAll, WhenAny, While,
+};
+use input::{Button, ControllerButton, ControllerHat, HatState, Key, MouseButton};
+
+fn parse(text: &str) -> Result<Behavior<String>, ai_behavior::ParseError> {
+    Behavior::parse(text, |s| {
+        if s == "unknown" {
+            Err(format!("Unknown action `{}`", s))
+        } else {
+            Ok(s.to_string())
+        }
+    })
+}
+
+#[test]
+fn parse_text() {
+    let text = "
+        ; Guard behavior.
+        (sequence
+          (wait 1.5)
+          (action jump)
+          (if (action \"see enemy\")
+            (action attack)
+            (wait-for-pressed (keyboard 97))))
+    ";
+    assert_eq!(
+        parse(text).unwrap(),
+        Sequence(vec![
+            Wait(1.5),
+            Action("jump".into()),
+            If(
+                Box::new(Action("see enemy".into())),
+                Box::new(Action("attack".into())),
+                Box::new(WaitForPressed(Button::Keyboard(Key::A))),
+            ),
+        ])
+    );
+}
+
+#[test]
+fn parse_errors() {
+    let err = parse("(sequence\n  (wiat 1))").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 4));
+    assert_eq!(err.to_string(), "2:4: Unknown behavior `wiat`");
+
+    let err = parse("(sequence (action unknown))").unwrap_err();
+    assert_eq!((err.line, err.column), (1, 19));
+    assert_eq!(err.message, "Unknown action `unknown`");
+
+    let text = format!("{}(wait 1){}", "(fail ".repeat(300), ")".repeat(300));
+    let err = parse(&text).unwrap_err();
+    assert_eq!((err.line, err.column), (1, 1537));
+    assert_eq!(err.message, "Behaviors are nested too deeply");
+    assert!(parse(&format!(
+        "{}(wait 1){}",
+        "(fail ".repeat(255),
+        ")".repeat(255)
+    ))
+    .is_ok());
+
+    let err = parse("(wait x)").unwrap_err();
+    assert_eq!(err.message, "Expected seconds, found `x`");
+
+    let err = parse("(wait 1) (wait 2)").unwrap_err();
+    assert_eq!((err.line, err.column), (1, 10));
+}
+
+#[test]
+fn print_round_trip() {
+    let b: Behavior<String> = While(
+        Box::new(WaitForever),
+        vec![
+            Select(vec![
+                Fail(Box::new(Action("a b".into()))),
+                AlwaysSucceed(Box::new(Action("say \"hi\"".into()))),
+            ]),
+            WhenAll(vec![
+                WaitForReleased(Button::Mouse(MouseButton::Left)),
+                WaitForPressed(Button::Controller(ControllerButton { id: 1, button: 2 })),
+                WaitForPressed(Button::Hat(ControllerHat {
+                    id: 0,
+                    state: HatState::LeftUp,
+                    which: 1,
+                })),
+            ]),
+            WhenAny(vec![]),
+            Select(vec![]),
+            Sequence(vec![]),
+            While(Box::new(WaitForever), vec![]),
+            After(vec![Wait(0.1), Action("".into())]),
+        ],
+    );
+    let text = b.to_text(|a| a.clone());
+    assert_eq!(parse(&text).unwrap(), b);
+    assert_eq!(
+        Sequence(vec![Wait(1.0), Action("jump".to_string())]).to_text(|a| a.clone()),
+        "(sequence\n  (wait 1)\n  (action jump))\n"
+    );
+}
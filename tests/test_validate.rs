@@ -0,0 +1,32 @@
+use ai_behavior::{Action, Behavior, If, Select, Sequence, Stats, Wait, WaitForever, While};
+
+#[test]
+fn validate() {
+    let b: Behavior<()> = Sequence(vec![
+        Wait(1.0),
+        If(
+            Box::new(Select(vec![])),
+            Box::new(Wait(-1.0)),
+            Box::new(While(Box::new(WaitForever), vec![])),
+        ),
+    ]);
+    let errors = b.validate().unwrap_err();
+    let paths: Vec<_> = errors.iter().map(|err| err.path.clone()).collect();
+    assert_eq!(paths, vec![vec![1, 0], vec![1, 1], vec![1, 2]]);
+    assert_eq!(errors[0].to_string(), "[1, 0]: `Select` has no behaviors");
+    assert_eq!(Action(()).validate(), Ok(()));
+}
+
+#[test]
+fn stats() {
+    let b = Sequence(vec![Wait(1.0), Select(vec![Action(1), Action(2)])]);
+    assert_eq!(
+        b.stats(),
+        Stats {
+            nodes: 5,
+            depth: 3,
+            actions: 2,
+            leaves: 3,
+        }
+    );
+}
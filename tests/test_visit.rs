@@ -0,0 +1,102 @@
+use ai_behavior::{
+    Action, Behavior, If, MapError, Select, Sequence, Visitor, Wait, WaitForever, While,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+enum Act {
+    Jump,
+    Run,
+}
+
+fn parse(name: &str) -> Result<Act, String> {
+    match name {
+        "jump" => Ok(Act::Jump),
+        "run" => Ok(Act::Run),
+        _ => Err(format!("Unknown action `{}`", name)),
+    }
+}
+
+#[test]
+fn map() {
+    let b = While(Box::new(WaitForever), vec![Action(1), Wait(1.0), Action(2)]);
+    assert_eq!(
+        b.map(|a| a * 10),
+        While(
+            Box::new(WaitForever),
+            vec![Action(10), Wait(1.0), Action(20)]
+        )
+    );
+}
+
+#[test]
+fn try_map() {
+    let b = Sequence(vec![Action("jump"), Select(vec![Action("run")])]);
+    assert_eq!(
+        b.try_map(parse),
+        Ok(Sequence(vec![
+            Action(Act::Jump),
+            Select(vec![Action(Act::Run)])
+        ]))
+    );
+
+    let b = If(
+        Box::new(Action("jump")),
+        Box::new(Action("fly")),
+        Box::new(While(Box::new(Action("swim")), vec![Action("run")])),
+    );
+    assert_eq!(
+        b.try_map(parse),
+        Err(vec![
+            MapError {
+                path: vec![1],
+                error: "Unknown action `fly`".to_string(),
+            },
+            MapError {
+                path: vec![2, 0],
+                error: "Unknown action `swim`".to_string(),
+            },
+        ])
+    );
+}
+
+struct Paths(Vec<String>);
+
+impl<A> Visitor<A> for Paths {
+    fn enter(&mut self, path: &[usize], _: &Behavior<A>) {
+        self.0.push(format!("enter {:?}", path));
+    }
+
+    fn exit(&mut self, path: &[usize], _: &Behavior<A>) {
+        self.0.push(format!("exit {:?}", path));
+    }
+}
+
+#[test]
+fn visit() {
+    let b = While(Box::new(WaitForever), vec![Sequence(vec![Action(())])]);
+    let mut paths = Paths(vec![]);
+    b.visit(&mut paths);
+    assert_eq!(
+        paths.0,
+        vec![
+            "enter []",
+            "enter [0]",
+            "exit [0]",
+            "enter [1]",
+            "enter [1, 0]",
+            "exit [1, 0]",
+            "exit [1]",
+            "exit []",
+        ]
+    );
+}
+
+#[test]
+fn fold() {
+    let b = Sequence(vec![Wait(1.0), Select(vec![Wait(2.0), Action(())])]);
+    let total = b.fold(|b, children: Vec<f64>| match *b {
+        Wait(dt) => dt,
+        _ => children.iter().sum(),
+    });
+    assert_eq!(total, 3.0);
+}
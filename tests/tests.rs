@@ -1,4 +1,34 @@
 extern crate ai_behavior;
 extern crate input;
 
+mod common;
+mod test_builder;
+#[cfg(feature = "cli")]
+mod test_cli;
+mod test_coverage;
+mod test_diff;
+mod test_dot;
 mod test_events;
+mod test_failure;
+mod test_future;
+#[cfg(feature = "fuzz")]
+mod test_fuzz;
+mod test_handler;
+mod test_macros;
+#[cfg(feature = "mock")]
+mod test_mock;
+mod test_observer;
+mod test_optimize;
+mod test_profile;
+mod test_reload;
+mod test_render;
+mod test_replay;
+mod test_reset;
+mod test_save;
+#[cfg(feature = "schema")]
+mod test_schema;
+mod test_snapshot;
+mod test_state;
+mod test_text;
+mod test_validate;
+mod test_visit;